@@ -33,3 +33,27 @@ pub fn is_fading(memory: &Memory) -> bool {
         _ => compute_decay_score(memory) < 0.1,
     }
 }
+
+/// Retrieval-time strength: how much a memory should count toward a search
+/// ranking right now, fusing recency half-life, access frequency, and how
+/// confident we were when it was written.
+///
+/// `confidence * exp(-ln(2) * age_days / half_life) * (1 + log(1 + access_count))`
+///
+/// `Never` decaying memories (promissory commitments) skip the recency term
+/// entirely so they don't quietly outrank themselves out of recall.
+pub fn effective_strength(memory: &Memory) -> f64 {
+    let recency = match memory.decay_class.half_life_days() {
+        Some(half_life) => {
+            let age_days = (Utc::now() - memory.last_accessed).num_hours() as f64 / 24.0;
+            (-std::f64::consts::LN_2 * age_days / half_life).exp()
+        }
+        None => 1.0,
+    };
+    let frequency = 1.0 + (1.0 + memory.access_count as f64).ln();
+    memory.confidence * recency * frequency
+}
+
+/// Default floor below which `forget` considers a memory stale enough to
+/// archive automatically during `reflect`.
+pub const FORGET_THRESHOLD: f64 = 0.05;
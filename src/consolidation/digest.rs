@@ -1,16 +1,20 @@
-use std::path::Path;
 use anyhow::Result;
 use chrono::NaiveDate;
 
 use crate::consolidation::journal;
 use crate::extract;
+use crate::store::backend::Storage;
 
-/// Consolidate old journal entries into a weekly digest
+/// Consolidate old journal entries into a weekly digest. Returns the report
+/// line plus the total consolidation token usage across every digest
+/// written, so `reflect` can feed it into the `claude_memory.consolidation_tokens`
+/// metric.
+#[tracing::instrument(skip(backend, api_key))]
 pub async fn consolidate_journals(
-    store_path: &Path,
+    backend: &dyn Storage,
     api_key: &str,
-) -> Result<Option<String>> {
-    let stale = journal::stale_journals(store_path, 14)?;
+) -> Result<Option<(String, u64)>> {
+    let stale = journal::stale_journals(backend, 14).await?;
     if stale.is_empty() {
         return Ok(None);
     }
@@ -24,12 +28,13 @@ pub async fn consolidate_journals(
     }
 
     let mut digests_written = Vec::new();
+    let mut total_tokens = 0u64;
 
     for (week_key, dates) in &week_groups {
         // Read all journal entries for this week
         let mut combined = String::new();
         for date in dates {
-            let content = crate::store::markdown::read_journal(store_path, date)?;
+            let content = crate::store::markdown::read_journal(backend, date).await?;
             combined.push_str(&content);
             combined.push('\n');
         }
@@ -39,17 +44,21 @@ pub async fn consolidate_journals(
         }
 
         // Call Haiku to consolidate
-        let digest = extract::consolidate_entries(&combined, api_key).await?;
+        let consolidation = extract::consolidate_entries(&combined, api_key).await?;
+        total_tokens += consolidation.total_tokens;
 
-        // Write digest file
-        let digest_dir = store_path.join("digests");
-        std::fs::create_dir_all(&digest_dir)?;
-        let file = digest_dir.join(format!("{}.md", week_key));
-        std::fs::write(&file, format!("# Week {}\n\n{}\n", week_key, digest))?;
+        // Write digest file through the configured backend, so consolidated
+        // relationship/lesson content lands in S3/Garage under
+        // MEMORY_BACKEND=s3 rather than on local disk. `backend` is already
+        // the `EncryptingBackend`-wrapped store when MEMORY_STORE_KEY is
+        // set, so blob_put seals this the same as every other writer —
+        // don't seal it again here.
+        let plaintext = format!("# Week {}\n\n{}\n", week_key, consolidation.text);
+        backend.blob_put(&format!("digests/{}.md", week_key), plaintext.into_bytes()).await?;
 
         // Archive the original journals
         for date in dates {
-            journal::archive_journal(store_path, date)?;
+            journal::archive_journal(backend, date).await?;
         }
 
         digests_written.push(week_key.clone());
@@ -58,10 +67,13 @@ pub async fn consolidate_journals(
     if digests_written.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(format!(
-            "Consolidated {} weeks: {}",
-            digests_written.len(),
-            digests_written.join(", ")
+        Ok(Some((
+            format!(
+                "Consolidated {} weeks: {}",
+                digests_written.len(),
+                digests_written.join(", ")
+            ),
+            total_tokens,
         )))
     }
 }
@@ -1,43 +1,41 @@
-use std::path::Path;
 use anyhow::Result;
 use chrono::{NaiveDate, Utc, Duration};
 
-/// List journal dates that exist in the store
-pub fn list_journal_dates(store_path: &Path) -> Result<Vec<NaiveDate>> {
-    let journal_dir = store_path.join("journal");
-    if !journal_dir.exists() {
-        return Ok(Vec::new());
-    }
+use crate::store::backend::Storage;
 
-    let mut dates = Vec::new();
-    for entry in std::fs::read_dir(&journal_dir)? {
-        let entry = entry?;
-        let name = entry.file_name().to_string_lossy().to_string();
-        if let Some(date_str) = name.strip_suffix(".md") {
+/// List journal dates that exist in the store. Goes through `Storage`
+/// rather than reading `journal/` off disk directly, so this sees the same
+/// dates whether entries landed on local disk, S3/Garage, or (transparently)
+/// decrypted from an `EncryptingBackend`. Each date is a directory of
+/// per-entry blobs (`journal/<date>/<entry>.md`, see `write_journal_entry`),
+/// so this dedupes the date component rather than expecting one blob per day.
+pub async fn list_journal_dates(backend: &dyn Storage) -> Result<Vec<NaiveDate>> {
+    let mut dates = std::collections::BTreeSet::new();
+    for key in backend.blob_list("journal/").await? {
+        if let Some(date_str) = key.strip_prefix("journal/").and_then(|rest| rest.split('/').next()) {
             if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                dates.push(date);
+                dates.insert(date);
             }
         }
     }
-    dates.sort();
-    Ok(dates)
+    Ok(dates.into_iter().collect())
 }
 
 /// Get journal dates older than N days that haven't been digested
-pub fn stale_journals(store_path: &Path, max_age_days: i64) -> Result<Vec<NaiveDate>> {
+pub async fn stale_journals(backend: &dyn Storage, max_age_days: i64) -> Result<Vec<NaiveDate>> {
     let cutoff = Utc::now().date_naive() - Duration::days(max_age_days);
-    let dates = list_journal_dates(store_path)?;
+    let dates = list_journal_dates(backend).await?;
     Ok(dates.into_iter().filter(|d| *d < cutoff).collect())
 }
 
 /// Read the last N journal files and concatenate their contents
-pub fn recent_journals(store_path: &Path, count: usize) -> Result<String> {
-    let dates = list_journal_dates(store_path)?;
+pub async fn recent_journals(backend: &dyn Storage, count: usize) -> Result<String> {
+    let dates = list_journal_dates(backend).await?;
     let recent: Vec<_> = dates.iter().rev().take(count).collect();
 
     let mut combined = String::new();
     for date in recent.iter().rev() {
-        let content = crate::store::markdown::read_journal(store_path, date)?;
+        let content = crate::store::markdown::read_journal(backend, date).await?;
         if !content.is_empty() {
             combined.push_str(&content);
             combined.push('\n');
@@ -46,17 +44,17 @@ pub fn recent_journals(store_path: &Path, count: usize) -> Result<String> {
     Ok(combined)
 }
 
-/// Move a journal file to the archive
-pub fn archive_journal(store_path: &Path, date: &NaiveDate) -> Result<()> {
-    let src = store_path
-        .join("journal")
-        .join(format!("{}.md", date.format("%Y-%m-%d")));
-    let dst_dir = store_path.join("archive").join("journal");
-    std::fs::create_dir_all(&dst_dir)?;
-    let dst = dst_dir.join(format!("{}.md", date.format("%Y-%m-%d")));
+/// Move a journal day's entries to the archive
+pub async fn archive_journal(backend: &dyn Storage, date: &NaiveDate) -> Result<()> {
+    let src_prefix = format!("journal/{}/", date.format("%Y-%m-%d"));
+    let dst_prefix = format!("archive/journal/{}/", date.format("%Y-%m-%d"));
 
-    if src.exists() {
-        std::fs::rename(&src, &dst)?;
+    for src_key in backend.blob_list(&src_prefix).await? {
+        let Some(suffix) = src_key.strip_prefix(&src_prefix) else { continue };
+        if let Some(bytes) = backend.blob_fetch(&src_key).await? {
+            backend.blob_put(&format!("{}{}", dst_prefix, suffix), bytes).await?;
+            backend.blob_delete(&src_key).await?;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,30 @@
+use crate::types::Memory;
+
+/// Cosine similarity (from whichever `VectorBackend` is active) above which
+/// two memories sharing the same `person`/`project` are treated as the same
+/// fact restated rather than two distinct ones.
+pub const DUPLICATE_SIMILARITY: f32 = 0.93;
+
+/// CRDT-style union of a duplicate into its canonical memory: every field
+/// merges independently by a rule that doesn't depend on which replica ran
+/// the merge or in which order, so two devices that each detect and fold the
+/// same duplicate converge on the same result instead of one's write
+/// clobbering the other's.
+///
+/// - `access_count`: grow-only counter, summed rather than maxed
+/// - `last_accessed` / `confidence`: last-writer-wins, both keyed off
+///   whichever side was accessed more recently
+/// - `created`: earliest-wins
+/// - `content`, `gate`, `person`, `project`, `decay_class`, `id`: kept from
+///   `canonical`, since both sides are assumed to already agree on what the
+///   memory is about
+pub fn merge(canonical: &Memory, duplicate: &Memory) -> Memory {
+    let mut merged = canonical.clone();
+    merged.access_count = canonical.access_count.saturating_add(duplicate.access_count);
+    merged.created = canonical.created.min(duplicate.created);
+    if duplicate.last_accessed > canonical.last_accessed {
+        merged.last_accessed = duplicate.last_accessed;
+        merged.confidence = duplicate.confidence;
+    }
+    merged
+}
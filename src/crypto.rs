@@ -0,0 +1,62 @@
+use anyhow::Result;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+const NONCE_LEN: usize = 24;
+const SALT: &[u8] = b"claude-memory-kit/v1";
+
+/// A derived symmetric key for sealing/opening store blobs. Cheap to clone
+/// (just wraps the already-derived bytes) so it can be shared across tasks.
+#[derive(Clone)]
+pub struct StoreKey(chacha20poly1305::Key);
+
+impl StoreKey {
+    /// Derive a key from `MEMORY_STORE_KEY` using Argon2. Returns `None` when
+    /// the env var is unset, in which case callers fall back to plaintext.
+    pub fn from_env() -> Result<Option<Self>> {
+        let passphrase = match std::env::var("MEMORY_STORE_KEY") {
+            Ok(p) if !p.is_empty() => p,
+            _ => return Ok(None),
+        };
+
+        let mut key_bytes = [0u8; 32];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), SALT, &mut key_bytes)
+            .map_err(|e| anyhow::anyhow!("key derivation failed: {}", e))?;
+
+        Ok(Some(Self(chacha20poly1305::Key::from(key_bytes))))
+    }
+}
+
+/// Seal `plaintext` into a random-nonce-prefixed ciphertext blob:
+/// `nonce (24 bytes) || ciphertext`.
+pub fn seal(key: &StoreKey, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = XChaCha20Poly1305::new(&key.0);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("seal failed: {}", e))?;
+
+    let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Open a blob produced by `seal`.
+pub fn open(key: &StoreKey, blob: &[u8]) -> Result<Vec<u8>> {
+    if blob.len() < NONCE_LEN {
+        anyhow::bail!("ciphertext too short to contain a nonce");
+    }
+    let (nonce_bytes, ciphertext) = blob.split_at(NONCE_LEN);
+    let cipher = XChaCha20Poly1305::new(&key.0);
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| anyhow::anyhow!("open failed (wrong key or corrupt blob): {}", e))
+}
@@ -14,6 +14,7 @@ Return JSON array only, no other text:
 
 If nothing is worth remembering, return: []"#;
 
+#[tracing::instrument(skip(transcript, api_key))]
 pub async fn extract_memories(
     transcript: &str,
     api_key: &str,
@@ -72,10 +73,19 @@ const CONSOLIDATION_PROMPT: &str = r#"You are updating Claude's memory. Compress
 
 Write the digest as prose, not bullet points."#;
 
+/// Result of a consolidation call, including the API's reported token usage
+/// so callers can feed it into the `claude_memory.consolidation_tokens`
+/// metric without re-parsing the response.
+pub struct Consolidation {
+    pub text: String,
+    pub total_tokens: u64,
+}
+
+#[tracing::instrument(skip(entries, api_key))]
 pub async fn consolidate_entries(
     entries: &str,
     api_key: &str,
-) -> Result<String> {
+) -> Result<Consolidation> {
     let client = reqwest::Client::new();
 
     let body = serde_json::json!({
@@ -104,12 +114,23 @@ pub async fn consolidate_entries(
         .as_str()
         .unwrap_or("(consolidation produced no output)")
         .to_string();
+    let total_tokens = data["usage"]["input_tokens"].as_u64().unwrap_or(0)
+        + data["usage"]["output_tokens"].as_u64().unwrap_or(0);
 
-    Ok(text)
+    Ok(Consolidation { text, total_tokens })
 }
 
-const IDENTITY_PROMPT: &str = r#"Rewrite Claude's identity card based on these memories. ~200 tokens. First person. Capture: who this person is now, how to communicate with them, what's active, any open commitments. This should feel like waking up and immediately knowing who you are."#;
+/// `write_identity` (in `store::markdown`) unions paragraphs by a claim key
+/// derived from the text before each paragraph's first colon, so a later
+/// paragraph about "Alice" supersedes an earlier one instead of piling up
+/// next to it. That only works if every paragraph actually starts with a
+/// short topic heading, hence the explicit format instruction below —
+/// don't loosen this without updating `claim_key` to match.
+const IDENTITY_PROMPT: &str = r#"Rewrite Claude's identity card based on these memories. ~200 tokens. First person. Capture: who this person is now, how to communicate with them, what's active, any open commitments. This should feel like waking up and immediately knowing who you are.
+
+Format: one paragraph per topic, separated by a blank line, each starting with a short "Topic: " heading (e.g. "Alice: ...", "Communication style: ...", "Open commitments: ..."). Reuse the same heading across regenerations when the topic hasn't changed, so updates supersede old claims instead of duplicating them."#;
 
+#[tracing::instrument(skip(memories, api_key))]
 pub async fn regenerate_identity(
     memories: &str,
     api_key: &str,
@@ -4,6 +4,10 @@ mod store;
 mod tools;
 mod extract;
 mod consolidation;
+mod crypto;
+mod otel;
+mod workers;
+mod metrics;
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -15,32 +19,31 @@ use tracing::info;
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv::dotenv().ok();
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("claude_memory=info".parse()?)
-        )
-        .with_writer(std::io::stderr)
-        .init();
+    let metrics = otel::install()?;
+    let prom_metrics = metrics::install()?;
 
     let args: Vec<String> = std::env::args().collect();
     if args.iter().any(|a| a == "--extract") {
-        return run_extract().await;
+        return run_extract(metrics, prom_metrics).await;
     }
 
-    run_mcp_server().await
+    run_mcp_server(metrics, prom_metrics).await
 }
 
-async fn run_mcp_server() -> Result<()> {
+async fn run_mcp_server(metrics: Option<otel::Metrics>, prom_metrics: Option<Arc<metrics::PromMetrics>>) -> Result<()> {
     let store_path = resolve_store_path();
     let api_key = std::env::var("ANTHROPIC_API_KEY").unwrap_or_default();
-    let store = store::Store::init(&store_path).await?;
+    let store = store::Store::init(&store_path, metrics.as_ref()).await?;
 
     let server = server::MemoryServer {
         store_path,
         api_key,
         store: Arc::new(tokio::sync::Mutex::new(store)),
+        metrics,
+        prom_metrics,
+        workers: workers::Workers::new(),
     };
+    workers::Workers::spawn(server.clone());
 
     let transport = rmcp::transport::io::stdio();
     info!("starting claude-memory MCP server (stdio)");
@@ -52,7 +55,7 @@ async fn run_mcp_server() -> Result<()> {
     Ok(())
 }
 
-async fn run_extract() -> Result<()> {
+async fn run_extract(metrics: Option<otel::Metrics>, prom_metrics: Option<Arc<metrics::PromMetrics>>) -> Result<()> {
     let mut transcript = String::new();
     std::io::Read::read_to_string(&mut std::io::stdin(), &mut transcript)?;
     if transcript.trim().is_empty() {
@@ -66,11 +69,14 @@ async fn run_extract() -> Result<()> {
         return Ok(());
     }
 
-    let store = store::Store::init(&store_path).await?;
+    let store = store::Store::init(&store_path, metrics.as_ref()).await?;
     let server = server::MemoryServer {
         store_path,
         api_key,
         store: Arc::new(tokio::sync::Mutex::new(store)),
+        metrics,
+        prom_metrics,
+        workers: workers::Workers::new(),
     };
 
     match server.do_auto_extract(&transcript).await {
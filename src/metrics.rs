@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use prometheus::{Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+
+/// Prometheus text-format metrics for the `/metrics` scrape endpoint —
+/// distinct from `otel::Metrics`, which needs an external OTLP collector.
+/// This is what lets an operator watch which recall tier is actually
+/// answering queries and how the memory set is aging over a long-running
+/// session, without standing up a collector.
+#[derive(Clone)]
+pub struct PromMetrics {
+    registry: Registry,
+    recall_tier_hits: IntCounterVec,
+    recall_tier_latency: HistogramVec,
+    remember_total: IntCounterVec,
+    memories_total: IntGauge,
+    decay_score: Histogram,
+    fading_memories: IntGauge,
+}
+
+/// Starts the `/metrics` server when `MEMORY_METRICS_PORT` is set, mirroring
+/// how `otel::install` only wires up OTLP export when its endpoint env var
+/// is present — metrics collection here is opt-in, not a default cost every
+/// session pays.
+pub fn install() -> Result<Option<Arc<PromMetrics>>> {
+    let port = match std::env::var("MEMORY_METRICS_PORT") {
+        Ok(p) if !p.is_empty() => p.parse::<u16>()?,
+        _ => return Ok(None),
+    };
+    let metrics = Arc::new(PromMetrics::new()?);
+    metrics.clone().serve(port);
+    Ok(Some(metrics))
+}
+
+impl PromMetrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let recall_tier_hits = IntCounterVec::new(
+            Opts::new("claude_memory_recall_tier_hits_total", "Recall results returned by each tier (fts5, vector, graph, postgres, grep_fallback)"),
+            &["tier"],
+        )?;
+        let recall_tier_latency = HistogramVec::new(
+            HistogramOpts::new("claude_memory_recall_tier_latency_seconds", "Recall tier latency"),
+            &["tier"],
+        )?;
+        let remember_total = IntCounterVec::new(
+            Opts::new("claude_memory_remember_total", "remember calls, by write gate"),
+            &["gate"],
+        )?;
+        let memories_total = IntGauge::new("claude_memory_memories_total", "Total memories currently indexed")?;
+        let decay_score = Histogram::with_opts(
+            HistogramOpts::new("claude_memory_decay_score", "Distribution of compute_decay_score across all memories")
+                .buckets(vec![0.0, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]),
+        )?;
+        let fading_memories = IntGauge::new("claude_memory_fading_memories", "Memories currently flagged as fading by is_fading")?;
+
+        registry.register(Box::new(recall_tier_hits.clone()))?;
+        registry.register(Box::new(recall_tier_latency.clone()))?;
+        registry.register(Box::new(remember_total.clone()))?;
+        registry.register(Box::new(memories_total.clone()))?;
+        registry.register(Box::new(decay_score.clone()))?;
+        registry.register(Box::new(fading_memories.clone()))?;
+
+        Ok(Self {
+            registry,
+            recall_tier_hits,
+            recall_tier_latency,
+            remember_total,
+            memories_total,
+            decay_score,
+            fading_memories,
+        })
+    }
+
+    pub fn record_recall_tier(&self, tier: &str, hits: usize, elapsed_secs: f64) {
+        self.recall_tier_latency.with_label_values(&[tier]).observe(elapsed_secs);
+        self.recall_tier_hits.with_label_values(&[tier]).inc_by(hits as u64);
+    }
+
+    pub fn record_remember(&self, gate: &str) {
+        self.remember_total.with_label_values(&[gate]).inc();
+    }
+
+    pub fn set_memories_total(&self, count: i64) {
+        self.memories_total.set(count);
+    }
+
+    pub fn observe_decay_score(&self, score: f64) {
+        self.decay_score.observe(score);
+    }
+
+    pub fn set_fading_memories(&self, count: i64) {
+        self.fading_memories.set(count);
+    }
+
+    fn render(&self) -> Vec<u8> {
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&metric_families, &mut buffer) {
+            tracing::warn!("failed to encode prometheus metrics: {}", e);
+        }
+        buffer
+    }
+
+    /// Serve `/metrics` on a dedicated OS thread rather than the tokio
+    /// runtime — scraping is low-traffic and shouldn't share a reactor with
+    /// request-handling tasks, so a plain blocking server is simplest here.
+    pub fn serve(self: Arc<Self>, port: u16) {
+        std::thread::spawn(move || {
+            let server = match tiny_http::Server::http(("0.0.0.0", port)) {
+                Ok(s) => s,
+                Err(e) => {
+                    tracing::warn!("failed to start /metrics server on port {}: {}", port, e);
+                    return;
+                }
+            };
+            tracing::info!("serving Prometheus metrics on :{}/metrics", port);
+            for request in server.incoming_requests() {
+                let body = self.render();
+                let response = tiny_http::Response::from_data(body).with_header(
+                    tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+                        .expect("static header is valid"),
+                );
+                let _ = request.respond(response);
+            }
+        });
+    }
+}
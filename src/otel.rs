@@ -0,0 +1,108 @@
+use anyhow::Result;
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Installs the crate's `tracing` subscriber: stderr formatting always, plus
+/// an OTLP tracer/meter layer when `OTEL_EXPORTER_OTLP_ENDPOINT` is set. This
+/// replaces the bare `tracing_subscriber::fmt()` call that used to live in
+/// `main`, since the two layers have to be registered together.
+pub fn install() -> Result<Option<Metrics>> {
+    let filter = tracing_subscriber::EnvFilter::from_default_env()
+        .add_directive("claude_memory=info".parse()?);
+    let fmt_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+
+    let endpoint = match std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+        Ok(e) if !e.is_empty() => e,
+        _ => {
+            tracing_subscriber::registry()
+                .with(filter)
+                .with(fmt_layer)
+                .init();
+            return Ok(None);
+        }
+    };
+
+    let resource = opentelemetry_sdk::Resource::new(vec![KeyValue::new(
+        "service.name",
+        "claude-memory",
+    )]);
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint))
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource.clone()))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+    let meter_provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry_sdk::runtime::Tokio)
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint))
+        .with_resource(resource)
+        .build()?;
+    global::set_meter_provider(meter_provider);
+
+    let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+
+    tracing::info!("OpenTelemetry export enabled ({})", endpoint);
+    Ok(Some(Metrics::new()))
+}
+
+/// Counters and histograms for the things operators actually need to debug:
+/// why extraction produced 0 memories, why reflection stalled, how the API
+/// is behaving. Only built by `install()` once the OTLP meter provider is
+/// set; when `install()` returns `None` there is no `Metrics`, and callers
+/// holding `Option<Metrics>` skip recording.
+#[derive(Clone)]
+pub struct Metrics {
+    memories_extracted: opentelemetry::metrics::Counter<u64>,
+    consolidation_tokens: opentelemetry::metrics::Counter<u64>,
+    api_errors: opentelemetry::metrics::Counter<u64>,
+    store_latency: opentelemetry::metrics::Histogram<f64>,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let meter = global::meter("claude-memory");
+        Self {
+            memories_extracted: meter
+                .u64_counter("claude_memory.memories_extracted")
+                .with_description("Memories extracted from transcripts, by gate")
+                .init(),
+            consolidation_tokens: meter
+                .u64_counter("claude_memory.consolidation_tokens")
+                .with_description("Token usage reported by consolidation API calls")
+                .init(),
+            api_errors: meter
+                .u64_counter("claude_memory.api_errors")
+                .with_description("Anthropic API call failures, by call")
+                .init(),
+            store_latency: meter
+                .f64_histogram("claude_memory.store_latency_ms")
+                .with_description("Store read/write latency in milliseconds")
+                .init(),
+        }
+    }
+
+    pub fn memory_extracted(&self, gate: &str) {
+        self.memories_extracted.add(1, &[KeyValue::new("gate", gate.to_string())]);
+    }
+
+    pub fn consolidation_tokens(&self, tokens: u64) {
+        self.consolidation_tokens.add(tokens, &[]);
+    }
+
+    pub fn api_error(&self, call: &str) {
+        self.api_errors.add(1, &[KeyValue::new("call", call.to_string())]);
+    }
+
+    pub fn store_latency(&self, op: &str, millis: f64) {
+        self.store_latency.record(millis, &[KeyValue::new("op", op.to_string())]);
+    }
+}
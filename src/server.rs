@@ -9,12 +9,16 @@ use rmcp::{
 };
 
 use crate::store::Store;
+use crate::workers::Workers;
 
 #[derive(Clone)]
 pub struct MemoryServer {
     pub store_path: PathBuf,
     pub api_key: String,
     pub store: Arc<tokio::sync::Mutex<Store>>,
+    pub metrics: Option<crate::otel::Metrics>,
+    pub prom_metrics: Option<Arc<crate::metrics::PromMetrics>>,
+    pub workers: Arc<Workers>,
 }
 
 // MCP request types
@@ -65,9 +69,36 @@ pub struct AutoExtractRequest {
     pub transcript: String,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct WorkersRequest {
+    #[schemars(description = "\"get\" to report worker state (default), or \"set\" to tune parameters")]
+    pub action: Option<String>,
+    #[schemars(description = "With action=\"set\": new interval in seconds for both maintenance workers")]
+    pub sweep_interval_secs: Option<u64>,
+    #[schemars(description = "With action=\"set\": new effective-strength threshold for the decay sweep")]
+    pub fading_threshold: Option<f64>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ConsolidateRequest {
+    #[schemars(description = "Optional: reason for triggering consolidation")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct QueryGraphRequest {
+    #[schemars(description = "Person to anchor the traversal on (optional, but person or project is required)")]
+    pub person: Option<String>,
+    #[schemars(description = "Project to anchor the traversal on (optional, but person or project is required)")]
+    pub project: Option<String>,
+    #[schemars(description = "Max hops to traverse from the anchor (default 2, capped at 4)")]
+    pub depth: Option<u32>,
+}
+
 #[tool(tool_box)]
 impl MemoryServer {
     #[tool(description = "Store a new memory. Must pass a write gate: behavioral (changes future actions), relational (about a person), epistemic (lesson learned), or promissory (commitment made). Write in first person.")]
+    #[tracing::instrument(skip(self, req))]
     async fn remember(
         &self, #[tool(aggr)] req: RememberRequest,
     ) -> String {
@@ -81,6 +112,7 @@ impl MemoryServer {
     }
 
     #[tool(description = "Search memories. Uses FTS5 for keywords, Qdrant for semantic similarity, and Neo4j for relational connections. Returns ranked results with IDs.")]
+    #[tracing::instrument(skip(self, req))]
     async fn recall(
         &self, #[tool(aggr)] req: RecallRequest,
     ) -> String {
@@ -91,6 +123,7 @@ impl MemoryServer {
     }
 
     #[tool(description = "Trigger memory consolidation. Compresses old journal entries into digests, regenerates identity card from recent memories. Runs Haiku for compression.")]
+    #[tracing::instrument(skip(self, _req))]
     async fn reflect(
         &self, #[tool(aggr)] _req: ReflectRequest,
     ) -> String {
@@ -101,6 +134,7 @@ impl MemoryServer {
     }
 
     #[tool(description = "Load identity card. Returns who you are in relation to this person and project (~200 tokens). On first session, returns a priming message.")]
+    #[tracing::instrument(skip(self, _req))]
     async fn identity(
         &self, #[tool(aggr)] _req: IdentityRequest,
     ) -> String {
@@ -111,6 +145,7 @@ impl MemoryServer {
     }
 
     #[tool(description = "Explicitly forget a memory. Requires the memory ID (from recall) and a reason. Memory is archived, not deleted.")]
+    #[tracing::instrument(skip(self, req))]
     async fn forget(
         &self, #[tool(aggr)] req: ForgetRequest,
     ) -> String {
@@ -121,6 +156,7 @@ impl MemoryServer {
     }
 
     #[tool(description = "Extract memories from a conversation transcript. Uses Haiku to identify memories that pass write gates. Called automatically by session hooks.")]
+    #[tracing::instrument(skip(self, req))]
     async fn auto_extract(
         &self, #[tool(aggr)] req: AutoExtractRequest,
     ) -> String {
@@ -129,6 +165,42 @@ impl MemoryServer {
             Err(e) => format!("Error: {}", e),
         }
     }
+
+    #[tool(description = "Run a structured relational query over the graph store, anchored on a person or project — for questions like 'who did I make commitments to about project X' that a fuzzy recall query can't express. Returns connected memories with their relationship to the anchor.")]
+    #[tracing::instrument(skip(self, req))]
+    async fn query_graph(
+        &self, #[tool(aggr)] req: QueryGraphRequest,
+    ) -> String {
+        match self.do_query_graph(
+            req.person.as_deref(), req.project.as_deref(), req.depth.unwrap_or(2),
+        ).await {
+            Ok(msg) => msg,
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Query or tune the background maintenance workers (decay sweep, store reconciliation). action=\"get\" (default) reports last run and items processed/changed; action=\"set\" updates sweep_interval_secs and/or fading_threshold live, no restart needed.")]
+    #[tracing::instrument(skip(self, req))]
+    async fn workers(
+        &self, #[tool(aggr)] req: WorkersRequest,
+    ) -> String {
+        self.do_workers(
+            req.action.as_deref().unwrap_or("get"),
+            req.sweep_interval_secs,
+            req.fading_threshold,
+        ).await
+    }
+
+    #[tool(description = "Find and merge near-duplicate memories (same person/project, high semantic similarity) using CRDT-style union so access_count/confidence/last_accessed survive the merge. remember() already links obvious repeats on write; this is for duplicates that predate that check or that crept in via auto_extract.")]
+    #[tracing::instrument(skip(self, _req))]
+    async fn consolidate(
+        &self, #[tool(aggr)] _req: ConsolidateRequest,
+    ) -> String {
+        match self.do_consolidate().await {
+            Ok(msg) => msg,
+            Err(e) => format!("Error: {}", e),
+        }
+    }
 }
 
 #[tool(tool_box)]
@@ -136,13 +208,16 @@ impl ServerHandler for MemoryServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some(
-                "Claude's persistent memory system. 6 tools: \
-                 remember (store with write gates), \
-                 recall (tri-store search: FTS5 + Qdrant vectors + Neo4j graph), \
+                "Claude's persistent memory system. 9 tools: \
+                 remember (store with write gates, auto-links near-duplicates), \
+                 recall (tri-store search: FTS5 + vector similarity + Neo4j graph), \
+                 query_graph (structured multi-hop traversal anchored on a person/project), \
                  reflect (consolidate and compress memories), \
+                 consolidate (merge near-duplicate memories with CRDT semantics), \
                  identity (load who-am-I card), \
                  forget (archive with reason), \
-                 auto_extract (pull memories from transcript). \
+                 auto_extract (pull memories from transcript), \
+                 workers (inspect/tune background maintenance). \
                  Memories are first-person prose, not structured data. \
                  Call identity at session start. Call remember when something matters."
                     .into(),
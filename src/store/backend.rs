@@ -0,0 +1,341 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Storage primitives the rest of the crate needs, independent of where the
+/// store actually lives. `blob_*` is addressed by caller-chosen keys
+/// (journal files, long-term memory files, identity cards, checkpoints);
+/// `row_*` is a small key-value API for index-like lookups that don't need
+/// a full blob scan. Implementations must be safe to share across tasks.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn blob_put(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+    async fn blob_fetch(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    async fn blob_list(&self, prefix: &str) -> Result<Vec<String>>;
+    async fn blob_delete(&self, key: &str) -> Result<()>;
+
+    async fn row_put(&self, table: &str, key: &str, value: Vec<u8>) -> Result<()>;
+    async fn row_get(&self, table: &str, key: &str) -> Result<Option<Vec<u8>>>;
+}
+
+/// The original layout: blobs are files under the store directory, rows are
+/// files under `.rows/<table>/<key>`. This is what `$HOME/.claude-memory/store`
+/// has always meant.
+pub struct FilesystemBackend {
+    root: std::path::PathBuf,
+}
+
+impl FilesystemBackend {
+    pub fn new(root: std::path::PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path_for(&self, key: &str) -> std::path::PathBuf {
+        self.root.join(key)
+    }
+
+    fn row_path(&self, table: &str, key: &str) -> std::path::PathBuf {
+        self.root.join(".rows").join(table).join(key)
+    }
+}
+
+#[async_trait]
+impl Storage for FilesystemBackend {
+    async fn blob_put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.path_for(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, bytes)?;
+        Ok(())
+    }
+
+    async fn blob_fetch(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path_for(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read(&path)?))
+    }
+
+    async fn blob_list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.path_for(prefix);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut keys = Vec::new();
+        for entry in walkdir::WalkDir::new(&dir).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() {
+                if let Ok(rel) = entry.path().strip_prefix(&self.root) {
+                    keys.push(rel.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn blob_delete(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    async fn row_put(&self, table: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        let path = self.row_path(table, key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, value)?;
+        Ok(())
+    }
+
+    async fn row_get(&self, table: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.row_path(table, key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read(&path)?))
+    }
+}
+
+/// Same primitives backed by an S3-compatible object store (AWS S3, Garage,
+/// MinIO, ...), so a store can be shared across machines instead of living
+/// under one `$HOME`. Configured from `MEMORY_S3_*` env vars, mirroring how
+/// `EmbeddingStore`/`GraphStore` read their own connection config.
+pub struct S3Backend {
+    client: object_store::aws::AmazonS3,
+    prefix: String,
+}
+
+impl S3Backend {
+    pub async fn connect() -> Result<Self> {
+        let bucket = std::env::var("MEMORY_S3_BUCKET")
+            .map_err(|_| anyhow::anyhow!("MEMORY_S3_BUCKET not set"))?;
+        let endpoint = std::env::var("MEMORY_S3_ENDPOINT").ok();
+        let access_key = std::env::var("MEMORY_S3_ACCESS_KEY")
+            .map_err(|_| anyhow::anyhow!("MEMORY_S3_ACCESS_KEY not set"))?;
+        let secret_key = std::env::var("MEMORY_S3_SECRET_KEY")
+            .map_err(|_| anyhow::anyhow!("MEMORY_S3_SECRET_KEY not set"))?;
+        let region = std::env::var("MEMORY_S3_REGION").unwrap_or_else(|_| "garage".into());
+        let prefix = std::env::var("MEMORY_S3_PREFIX").unwrap_or_default();
+
+        let mut builder = object_store::aws::AmazonS3Builder::new()
+            .with_bucket_name(&bucket)
+            .with_access_key_id(&access_key)
+            .with_secret_access_key(&secret_key)
+            .with_region(&region)
+            .with_allow_http(true);
+        if let Some(endpoint) = endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+
+        let client = builder.build()?;
+        Ok(Self { client, prefix })
+    }
+
+    fn object_path(&self, key: &str) -> object_store::path::Path {
+        object_store::path::Path::from(format!("{}{}", self.prefix, key))
+    }
+}
+
+#[async_trait]
+impl Storage for S3Backend {
+    async fn blob_put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        use object_store::ObjectStore;
+        self.client.put(&self.object_path(key), bytes.into()).await?;
+        Ok(())
+    }
+
+    async fn blob_fetch(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        use object_store::ObjectStore;
+        match self.client.get(&self.object_path(key)).await {
+            Ok(result) => Ok(Some(result.bytes().await?.to_vec())),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn blob_list(&self, prefix: &str) -> Result<Vec<String>> {
+        use futures_util::TryStreamExt;
+        use object_store::ObjectStore;
+        let mut keys = Vec::new();
+        let mut stream = self.client.list(Some(&self.object_path(prefix)));
+        while let Some(meta) = stream.try_next().await? {
+            // Strip `self.prefix` back off, same as `FilesystemBackend`
+            // returns keys relative to `self.root` rather than absolute
+            // paths — `blob_fetch`/`blob_delete` re-apply the prefix via
+            // `object_path()`, so a caller that lists then re-fetches by
+            // the returned key (`OpLog::load_state`/`latest_checkpoint`)
+            // would otherwise double-prefix and always get `None` back.
+            let location = meta.location.to_string();
+            let key = location.strip_prefix(&self.prefix).unwrap_or(&location);
+            keys.push(key.to_string());
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn blob_delete(&self, key: &str) -> Result<()> {
+        use object_store::ObjectStore;
+        match self.client.delete(&self.object_path(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn row_put(&self, table: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        self.blob_put(&format!(".rows/{}/{}", table, key), value).await
+    }
+
+    async fn row_get(&self, table: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        self.blob_fetch(&format!(".rows/{}/{}", table, key)).await
+    }
+}
+
+/// Wraps another `Storage` and seals/opens every blob with an authenticated
+/// cipher, so the underlying backend (local disk or a shared S3/Garage
+/// bucket) only ever sees ciphertext. Row values are left alone — they're
+/// small index lookups, not memory content. Transparent: if no key was
+/// configured, this wrapper is never constructed and callers get today's
+/// plaintext behavior straight from the inner backend.
+pub struct EncryptingBackend {
+    inner: std::sync::Arc<dyn Storage>,
+    key: crate::crypto::StoreKey,
+}
+
+impl EncryptingBackend {
+    pub fn new(inner: std::sync::Arc<dyn Storage>, key: crate::crypto::StoreKey) -> Self {
+        Self { inner, key }
+    }
+}
+
+#[async_trait]
+impl Storage for EncryptingBackend {
+    async fn blob_put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let sealed = crate::crypto::seal(&self.key, &bytes)?;
+        self.inner.blob_put(key, sealed).await
+    }
+
+    async fn blob_fetch(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.inner.blob_fetch(key).await? {
+            Some(sealed) => Ok(Some(crate::crypto::open(&self.key, &sealed)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn blob_list(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.blob_list(prefix).await
+    }
+
+    async fn blob_delete(&self, key: &str) -> Result<()> {
+        self.inner.blob_delete(key).await
+    }
+
+    async fn row_put(&self, table: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        self.inner.row_put(table, key, value).await
+    }
+
+    async fn row_get(&self, table: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        self.inner.row_get(table, key).await
+    }
+}
+
+/// Wraps another `Storage` and times every call into
+/// `claude_memory.store_latency_ms`, tagged by op name. Sits outermost (after
+/// `EncryptingBackend`) so the recorded latency is what callers actually
+/// feel, seal/open included. Not applied when `otel::install()` returned no
+/// `Metrics`: `connect` then returns the inner backend unwrapped, so no
+/// `Instant::now()` calls are paid.
+pub struct InstrumentedBackend {
+    inner: std::sync::Arc<dyn Storage>,
+    metrics: crate::otel::Metrics,
+}
+
+impl InstrumentedBackend {
+    pub fn new(inner: std::sync::Arc<dyn Storage>, metrics: crate::otel::Metrics) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn record(&self, op: &str, started: std::time::Instant) {
+        self.metrics.store_latency(op, started.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+#[async_trait]
+impl Storage for InstrumentedBackend {
+    async fn blob_put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let started = std::time::Instant::now();
+        let result = self.inner.blob_put(key, bytes).await;
+        self.record("blob_put", started);
+        result
+    }
+
+    async fn blob_fetch(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let started = std::time::Instant::now();
+        let result = self.inner.blob_fetch(key).await;
+        self.record("blob_fetch", started);
+        result
+    }
+
+    async fn blob_list(&self, prefix: &str) -> Result<Vec<String>> {
+        let started = std::time::Instant::now();
+        let result = self.inner.blob_list(prefix).await;
+        self.record("blob_list", started);
+        result
+    }
+
+    async fn blob_delete(&self, key: &str) -> Result<()> {
+        let started = std::time::Instant::now();
+        let result = self.inner.blob_delete(key).await;
+        self.record("blob_delete", started);
+        result
+    }
+
+    async fn row_put(&self, table: &str, key: &str, value: Vec<u8>) -> Result<()> {
+        let started = std::time::Instant::now();
+        let result = self.inner.row_put(table, key, value).await;
+        self.record("row_put", started);
+        result
+    }
+
+    async fn row_get(&self, table: &str, key: &str) -> Result<Option<Vec<u8>>> {
+        let started = std::time::Instant::now();
+        let result = self.inner.row_get(table, key).await;
+        self.record("row_get", started);
+        result
+    }
+}
+
+/// Pick the backend from `MEMORY_BACKEND` (`"fs"` by default, `"s3"` to talk
+/// to a Garage/MinIO/S3 bucket configured via `MEMORY_S3_*`), wrap it with
+/// `EncryptingBackend` if `MEMORY_STORE_KEY` is configured, then with
+/// `InstrumentedBackend` if OpenTelemetry export is enabled.
+pub async fn connect(
+    store_path: &std::path::Path,
+    metrics: Option<&crate::otel::Metrics>,
+) -> Result<std::sync::Arc<dyn Storage>> {
+    let backend: std::sync::Arc<dyn Storage> =
+        match std::env::var("MEMORY_BACKEND").unwrap_or_else(|_| "fs".into()).as_str() {
+            "s3" => {
+                let backend = S3Backend::connect().await?;
+                tracing::info!("memory store backend: s3 ({})", std::env::var("MEMORY_S3_BUCKET").unwrap_or_default());
+                std::sync::Arc::new(backend)
+            }
+            _ => std::sync::Arc::new(FilesystemBackend::new(store_path.to_path_buf())),
+        };
+
+    let backend = match crate::crypto::StoreKey::from_env()? {
+        Some(key) => {
+            tracing::info!("memory store encryption enabled");
+            std::sync::Arc::new(EncryptingBackend::new(backend, key)) as std::sync::Arc<dyn Storage>
+        }
+        None => backend,
+    };
+
+    Ok(match metrics {
+        Some(metrics) => std::sync::Arc::new(InstrumentedBackend::new(backend, metrics.clone())),
+        None => backend,
+    })
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use qdrant_client::qdrant::{
     CreateCollectionBuilder, Distance, PointStruct,
     SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder,
@@ -7,6 +8,8 @@ use qdrant_client::qdrant::{
 };
 use qdrant_client::Qdrant;
 
+use super::vector::{chunk_content, collapse_best_per_memory, VectorBackend};
+
 const COLLECTION: &str = "claude_memories";
 const VECTOR_SIZE: u64 = 384;
 
@@ -49,66 +52,77 @@ impl EmbeddingStore {
 
         Ok(Self { client, model })
     }
+}
 
-    pub async fn embed_and_store(
+#[async_trait]
+impl VectorBackend for EmbeddingStore {
+    /// Chunk `content` into one point per window so long memories (and
+    /// whole transcripts passed through `auto_extract`) don't get averaged
+    /// down into one diluted vector. Every chunk shares `memory_id` in its
+    /// payload plus a `chunk_index`, so `delete_point`'s memory_id filter
+    /// still removes all of them at once.
+    async fn embed_and_store(
         &self,
         memory_id: &str,
         content: &str,
         person: Option<&str>,
         project: Option<&str>,
     ) -> Result<()> {
-        let embeddings = self.model.embed(vec![content], None)?;
-        let vector = embeddings.into_iter().next()
-            .ok_or_else(|| anyhow::anyhow!("embedding failed"))?;
+        let chunks = chunk_content(content);
+        let embeddings = self.model.embed(chunks.clone(), None)?;
 
-        let mut payload = std::collections::HashMap::<String, qdrant_client::qdrant::Value>::new();
-        payload.insert("memory_id".into(), memory_id.to_string().into());
-        payload.insert("content".into(), content.to_string().into());
-        if let Some(p) = person {
-            payload.insert("person".into(), p.to_string().into());
-        }
-        if let Some(p) = project {
-            payload.insert("project".into(), p.to_string().into());
-        }
+        let points: Vec<PointStruct> = chunks.iter().zip(embeddings).enumerate()
+            .map(|(chunk_index, (chunk, vector))| {
+                let mut payload = std::collections::HashMap::<String, qdrant_client::qdrant::Value>::new();
+                payload.insert("memory_id".into(), memory_id.to_string().into());
+                payload.insert("chunk_index".into(), (chunk_index as i64).into());
+                payload.insert("content".into(), chunk.clone().into());
+                if let Some(p) = person {
+                    payload.insert("person".into(), p.to_string().into());
+                }
+                if let Some(p) = project {
+                    payload.insert("project".into(), p.to_string().into());
+                }
 
-        // Qdrant requires UUID or integer point IDs
-        let point_id = uuid::Uuid::new_v4().to_string();
-        let point = PointStruct::new(point_id, vector, payload);
+                // Qdrant requires UUID or integer point IDs
+                PointStruct::new(uuid::Uuid::new_v4().to_string(), vector, payload)
+            })
+            .collect();
 
         self.client.upsert_points(
-            UpsertPointsBuilder::new(COLLECTION, vec![point])
+            UpsertPointsBuilder::new(COLLECTION, points)
         ).await?;
         Ok(())
     }
 
-    pub async fn search_similar(
-        &self,
-        query: &str,
-        limit: u64,
-    ) -> Result<Vec<(String, f32)>> {
+    /// Search chunk points, then collapse back to one result per
+    /// `memory_id` keeping the best-scoring chunk — so a long memory
+    /// surfaces as soon as any one of its passages matches.
+    async fn search_similar(&self, query: &str, limit: u64) -> Result<Vec<(String, f32)>> {
         let embeddings = self.model.embed(vec![query], None)?;
         let vector = embeddings.into_iter().next()
             .ok_or_else(|| anyhow::anyhow!("embedding failed"))?;
 
+        // Over-fetch chunk points since several can belong to the same
+        // memory before we've collapsed them down to `limit` memories.
         let results = self.client.search_points(
-            SearchPointsBuilder::new(COLLECTION, vector, limit)
+            SearchPointsBuilder::new(COLLECTION, vector, limit * 4)
                 .with_payload(true),
         ).await?;
 
-        let mut out = Vec::new();
-        for point in results.result {
+        let hits = results.result.into_iter().map(|point| {
             let mem_id = point.payload.get("memory_id")
                 .and_then(|v| match &v.kind {
                     Some(qdrant_client::qdrant::value::Kind::StringValue(s)) => Some(s.clone()),
                     _ => None,
                 })
                 .unwrap_or_default();
-            out.push((mem_id, point.score));
-        }
-        Ok(out)
+            (mem_id, point.score)
+        });
+        Ok(collapse_best_per_memory(hits, limit as usize))
     }
 
-    pub async fn delete_point(&self, memory_id: &str) -> Result<()> {
+    async fn delete_point(&self, memory_id: &str) -> Result<()> {
         use qdrant_client::qdrant::{Condition, Filter};
         self.client.delete_points(
             DeletePointsBuilder::new(COLLECTION)
@@ -118,4 +132,16 @@ impl EmbeddingStore {
         ).await?;
         Ok(())
     }
+
+    async fn contains(&self, memory_id: &str) -> Result<bool> {
+        use qdrant_client::qdrant::{Condition, Filter, ScrollPointsBuilder};
+        let result = self.client.scroll(
+            ScrollPointsBuilder::new(COLLECTION)
+                .filter(Filter::must([
+                    Condition::matches("memory_id", memory_id.to_string()),
+                ]))
+                .limit(1),
+        ).await?;
+        Ok(!result.result.is_empty())
+    }
 }
@@ -112,44 +112,113 @@ impl GraphStore {
         Ok(related)
     }
 
+    /// Multi-hop traversal anchored on a person or project rather than a
+    /// single memory id, for the `query_graph` tool's structured relational
+    /// queries ("who did I make commitments to about project X"). Neo4j
+    /// can't bind a variable-length path range as a query parameter, so
+    /// `depth` is clamped and formatted into the Cypher text the same way
+    /// `add_edge` formats its (separately sanitized) relation type.
+    pub async fn find_related_to_anchor(
+        &self,
+        person: Option<&str>,
+        project: Option<&str>,
+        depth: u32,
+    ) -> Result<Vec<(String, String, String)>> {
+        if person.is_none() && project.is_none() {
+            anyhow::bail!("find_related_to_anchor needs a person or project anchor");
+        }
+        let depth = depth.clamp(1, 4);
+
+        let mut clauses = Vec::new();
+        if person.is_some() {
+            clauses.push("a.person = $person");
+        }
+        if project.is_some() {
+            clauses.push("a.project = $project");
+        }
+        let where_clause = clauses.join(" OR ");
+
+        let cypher = format!(
+            "MATCH (a:Memory)-[r*1..{depth}]-(b:Memory) \
+             WHERE {where_clause} \
+             RETURN DISTINCT b.id AS id, b.preview AS preview, \
+                    type(r[0]) AS relation \
+             LIMIT 20"
+        );
+
+        let mut q = query(&cypher);
+        if let Some(person) = person {
+            q = q.param("person", person);
+        }
+        if let Some(project) = project {
+            q = q.param("project", project);
+        }
+        let mut result = self.graph.execute(q).await?;
+
+        let mut related = Vec::new();
+        while let Some(row) = result.next().await? {
+            let id: String = row.get("id").unwrap_or_default();
+            let preview: String = row.get("preview").unwrap_or_default();
+            let relation: String = row.get("relation").unwrap_or_default();
+            related.push((id, relation, preview));
+        }
+        Ok(related)
+    }
+
+    /// Links `memory_id` to every other memory sharing its person/project,
+    /// returning the ids it linked to so the caller can log an
+    /// `Op::AddEdge` per edge and the op log records every edge created
+    /// here. `OpLog::rebuild` only reindexes memories; it does not recreate
+    /// these edges in Neo4j.
     pub async fn auto_link(
         &self,
         memory_id: &str,
         person: Option<&str>,
         project: Option<&str>,
-    ) -> Result<()> {
+    ) -> Result<Vec<String>> {
+        let mut linked = Vec::new();
+
         // Link to other memories about the same person
         if let Some(person) = person {
             if !person.is_empty() {
-                self.graph.run(
+                let mut result = self.graph.execute(
                     query(
                         "MATCH (a:Memory {id: $id}), \
                                (b:Memory {person: $person}) \
                          WHERE a <> b \
-                         MERGE (a)-[:RELATED_TO]->(b)"
+                         MERGE (a)-[:RELATED_TO]->(b) \
+                         RETURN b.id AS id"
                     )
                     .param("id", memory_id)
                     .param("person", person),
                 ).await?;
+                while let Some(row) = result.next().await? {
+                    linked.push(row.get::<String>("id").unwrap_or_default());
+                }
             }
         }
 
         // Link to other memories about the same project
         if let Some(project) = project {
             if !project.is_empty() {
-                self.graph.run(
+                let mut result = self.graph.execute(
                     query(
                         "MATCH (a:Memory {id: $id}), \
                                (b:Memory {project: $project}) \
                          WHERE a <> b \
-                         MERGE (a)-[:RELATED_TO]->(b)"
+                         MERGE (a)-[:RELATED_TO]->(b) \
+                         RETURN b.id AS id"
                     )
                     .param("id", memory_id)
                     .param("project", project),
                 ).await?;
+                while let Some(row) = result.next().await? {
+                    linked.push(row.get::<String>("id").unwrap_or_default());
+                }
             }
         }
-        Ok(())
+        linked.retain(|id| !id.is_empty());
+        Ok(linked)
     }
 
     pub async fn delete_node(&self, memory_id: &str) -> Result<()> {
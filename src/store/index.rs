@@ -0,0 +1,30 @@
+use anyhow::Result;
+
+use crate::types::Memory;
+
+/// Backend-agnostic interface over the memory row store plus its full-text
+/// index — the `MemoryIndex` tier's equivalent of `VectorBackend`. `Store`
+/// holds a `Box<dyn MemoryIndex>` rather than a concrete `SqliteStore`, so
+/// `do_recall`/`do_remember`/the consolidation and decay-sweep passes stay
+/// backend-agnostic and an operator can pick the engine at `Store::init`
+/// time without touching any of them.
+pub trait MemoryIndex: Send + Sync {
+    fn index_memory(&self, memory: &Memory) -> Result<()>;
+    fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<Memory>>;
+
+    /// Every memory currently indexed. Used by the decay sweep and
+    /// consolidation pass, which need to score/scan the whole set rather
+    /// than a search-ranked subset.
+    fn list_all(&self) -> Result<Vec<Memory>>;
+
+    fn touch_memory(&self, id: &str) -> Result<()>;
+    fn delete_memory(&self, id: &str) -> Result<Option<Memory>>;
+    fn get_memory(&self, id: &str) -> Result<Option<Memory>>;
+
+    /// Record that consolidation merged `duplicate_id` into `canonical_id`.
+    fn add_redirect(&self, duplicate_id: &str, canonical_id: &str) -> Result<()>;
+
+    /// Where a merged-away memory id now lives, if consolidation ever
+    /// absorbed it into another memory.
+    fn resolve_redirect(&self, id: &str) -> Result<Option<String>>;
+}
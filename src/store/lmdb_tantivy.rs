@@ -0,0 +1,189 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use heed::types::{SerdeJson, Str};
+use heed::{Database, Env, EnvOpenOptions};
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Schema, Field, STORED, STRING, TEXT};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, Term};
+
+use crate::store::index::MemoryIndex;
+use crate::types::Memory;
+
+/// Generous for a personal memory store; LMDB only grows the backing file
+/// to what's actually used, this is just the mmap reservation ceiling.
+const LMDB_MAP_SIZE: usize = 1 << 30;
+
+const TANTIVY_WRITER_HEAP_BYTES: usize = 50_000_000;
+
+/// `MemoryIndex` backed by LMDB for row storage (memories + redirects) and
+/// Tantivy for full-text search — a pure-Rust alternative to SQLite+FTS5 for
+/// embedded environments that can't carry FTS5's compile-time dependency.
+/// Row reads are a single mmap'd B-tree lookup; `search_fts` defers entirely
+/// to Tantivy's own ranking rather than re-deriving what FTS5 gets for free.
+pub struct LmdbTantivyIndex {
+    env: Env,
+    memories: Database<Str, SerdeJson<Memory>>,
+    redirects: Database<Str, Str>,
+    tantivy_index: Index,
+    tantivy_writer: Mutex<IndexWriter>,
+    tantivy_reader: IndexReader,
+    id_field: Field,
+    content_field: Field,
+    person_field: Field,
+    project_field: Field,
+}
+
+impl LmdbTantivyIndex {
+    pub fn open(store_path: &Path) -> Result<Self> {
+        let lmdb_dir = store_path.join("lmdb_index");
+        std::fs::create_dir_all(&lmdb_dir)?;
+        // Safety: we're the only process that opens this environment for
+        // this store path, matching the single-server-process assumption
+        // the rest of `Store` already makes (e.g. `OpLog`'s node id, which is
+        // persisted per store and shared by every process that opens it).
+        let env = unsafe {
+            EnvOpenOptions::new()
+                .map_size(LMDB_MAP_SIZE)
+                .max_dbs(2)
+                .open(&lmdb_dir)?
+        };
+
+        let mut wtxn = env.write_txn()?;
+        let memories: Database<Str, SerdeJson<Memory>> = env.create_database(&mut wtxn, Some("memories"))?;
+        let redirects: Database<Str, Str> = env.create_database(&mut wtxn, Some("redirects"))?;
+        wtxn.commit()?;
+
+        let tantivy_dir = store_path.join("tantivy_index");
+        std::fs::create_dir_all(&tantivy_dir)?;
+
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let content_field = schema_builder.add_text_field("content", TEXT);
+        let person_field = schema_builder.add_text_field("person", TEXT);
+        let project_field = schema_builder.add_text_field("project", TEXT);
+        let schema = schema_builder.build();
+
+        let dir = tantivy::directory::MmapDirectory::open(&tantivy_dir)?;
+        let tantivy_index = Index::open_or_create(dir, schema)?;
+        let tantivy_writer = tantivy_index.writer(TANTIVY_WRITER_HEAP_BYTES)?;
+        let tantivy_reader = tantivy_index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()?;
+
+        Ok(Self {
+            env,
+            memories,
+            redirects,
+            tantivy_index,
+            tantivy_writer: Mutex::new(tantivy_writer),
+            tantivy_reader,
+            id_field,
+            content_field,
+            person_field,
+            project_field,
+        })
+    }
+}
+
+impl MemoryIndex for LmdbTantivyIndex {
+    fn index_memory(&self, memory: &Memory) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.memories.put(&mut wtxn, &memory.id, memory)?;
+        wtxn.commit()?;
+
+        // Tantivy has no upsert-by-id; delete-then-add keyed on the id field
+        // is the standard way to reindex a changed document. person/project
+        // are indexed alongside content to match FTS5's `memories_fts(content,
+        // person, project)` table (sqlite.rs) — a query that only matches a
+        // person or project name should hit under either backend.
+        let mut writer = self.tantivy_writer.lock().unwrap();
+        writer.delete_term(Term::from_field_text(self.id_field, &memory.id));
+        writer.add_document(doc!(
+            self.id_field => memory.id.clone(),
+            self.content_field => memory.content.clone(),
+            self.person_field => memory.person.clone().unwrap_or_default(),
+            self.project_field => memory.project.clone().unwrap_or_default(),
+        ))?;
+        writer.commit()?;
+        Ok(())
+    }
+
+    fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<Memory>> {
+        let searcher = self.tantivy_reader.searcher();
+        let parser = QueryParser::for_index(
+            &self.tantivy_index,
+            vec![self.content_field, self.person_field, self.project_field],
+        );
+        let parsed = parser.parse_query(query)?;
+        let hits = searcher.search(&parsed, &TopDocs::with_limit(limit))?;
+
+        let rtxn = self.env.read_txn()?;
+        let mut results = Vec::new();
+        for (_score, doc_address) in hits {
+            let doc: tantivy::TantivyDocument = searcher.doc(doc_address)?;
+            let Some(id) = doc.get_first(self.id_field).and_then(|v| v.as_str()) else { continue };
+            if let Some(memory) = self.memories.get(&rtxn, id)? {
+                results.push(memory);
+            }
+        }
+        Ok(results)
+    }
+
+    fn list_all(&self) -> Result<Vec<Memory>> {
+        let rtxn = self.env.read_txn()?;
+        let mut results = Vec::new();
+        for entry in self.memories.iter(&rtxn)? {
+            let (_, memory) = entry?;
+            results.push(memory);
+        }
+        Ok(results)
+    }
+
+    fn touch_memory(&self, id: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        if let Some(mut memory) = self.memories.get(&wtxn, id)? {
+            memory.access_count += 1;
+            memory.last_accessed = chrono::Utc::now();
+            self.memories.put(&mut wtxn, id, &memory)?;
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    fn delete_memory(&self, id: &str) -> Result<Option<Memory>> {
+        let mut wtxn = self.env.write_txn()?;
+        let existing = self.memories.get(&wtxn, id)?;
+        if existing.is_some() {
+            self.memories.delete(&mut wtxn, id)?;
+        }
+        wtxn.commit()?;
+
+        if existing.is_some() {
+            let mut writer = self.tantivy_writer.lock().unwrap();
+            writer.delete_term(Term::from_field_text(self.id_field, id));
+            writer.commit()?;
+        }
+        Ok(existing)
+    }
+
+    fn get_memory(&self, id: &str) -> Result<Option<Memory>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.memories.get(&rtxn, id)?)
+    }
+
+    fn add_redirect(&self, duplicate_id: &str, canonical_id: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.redirects.put(&mut wtxn, duplicate_id, canonical_id)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    fn resolve_redirect(&self, id: &str) -> Result<Option<String>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.redirects.get(&rtxn, id)?.map(str::to_string))
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use instant_distance::{Builder, HnswMap, Search};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::vector::{chunk_content, collapse_best_per_memory, VectorBackend};
+
+const INDEX_FILE: &str = "vectors.json";
+
+/// Zero-dependency default for semantic recall: an HNSW index built over
+/// embeddings kept in a single JSON file next to `index.db`, instead of a
+/// Qdrant Cloud collection. `Store::init` falls back to this whenever
+/// `QDRANT_URL`/`QDRANT_API_KEY` aren't configured, so recall's vector tier
+/// still works offline.
+///
+/// The index is small enough (personal memory stores, not web-scale corpora)
+/// that rebuilding it from the point list on every search is simpler and
+/// plenty fast, rather than maintaining an incrementally-updatable graph on
+/// disk.
+pub struct LocalVectorStore {
+    index_path: PathBuf,
+    model: fastembed::TextEmbedding,
+    points: Mutex<Vec<StoredPoint>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredPoint {
+    memory_id: String,
+    vector: Vec<f32>,
+}
+
+#[derive(Clone)]
+struct EmbeddedPoint(Vec<f32>);
+
+impl instant_distance::Point for EmbeddedPoint {
+    fn distance(&self, other: &Self) -> f32 {
+        // Cosine distance: embeddings are already unit-length (fastembed's
+        // AllMiniLML6V2 output), so this reduces to 1 - dot product.
+        let dot: f32 = self.0.iter().zip(other.0.iter()).map(|(a, b)| a * b).sum();
+        1.0 - dot
+    }
+}
+
+impl LocalVectorStore {
+    pub async fn open(store_path: &Path) -> Result<Self> {
+        let index_path = store_path.join(INDEX_FILE);
+        let points = if index_path.exists() {
+            let bytes = tokio::fs::read(&index_path).await?;
+            serde_json::from_slice(&bytes)?
+        } else {
+            Vec::new()
+        };
+
+        let model = fastembed::TextEmbedding::try_new(
+            fastembed::InitOptions::new(fastembed::EmbeddingModel::AllMiniLML6V2)
+                .with_show_download_progress(false),
+        )?;
+
+        Ok(Self {
+            index_path,
+            model,
+            points: Mutex::new(points),
+        })
+    }
+
+    async fn persist(&self, points: &[StoredPoint]) -> Result<()> {
+        let bytes = serde_json::to_vec(points)?;
+        tokio::fs::write(&self.index_path, bytes).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VectorBackend for LocalVectorStore {
+    async fn embed_and_store(
+        &self,
+        memory_id: &str,
+        content: &str,
+        _person: Option<&str>,
+        _project: Option<&str>,
+    ) -> Result<()> {
+        let chunks = chunk_content(content);
+        let embeddings = self.model.embed(chunks, None)?;
+
+        let mut points = self.points.lock().await;
+        for vector in embeddings {
+            points.push(StoredPoint {
+                memory_id: memory_id.to_string(),
+                vector,
+            });
+        }
+        self.persist(&points).await
+    }
+
+    async fn search_similar(&self, query: &str, limit: u64) -> Result<Vec<(String, f32)>> {
+        let points = self.points.lock().await;
+        if points.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let embeddings = self.model.embed(vec![query], None)?;
+        let query_vector = embeddings.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("embedding failed"))?;
+
+        let (vectors, memory_ids): (Vec<EmbeddedPoint>, Vec<String>) = points.iter()
+            .map(|p| (EmbeddedPoint(p.vector.clone()), p.memory_id.clone()))
+            .unzip();
+        let map: HnswMap<EmbeddedPoint, String> = Builder::default().build(vectors, memory_ids);
+
+        let mut search = Search::default();
+        // Over-fetch chunk-level hits since several can belong to the same
+        // memory before we've collapsed them down to `limit` memories.
+        let hits = map.search(&EmbeddedPoint(query_vector), &mut search)
+            .take(limit as usize * 4)
+            .map(|item| (item.value.clone(), 1.0 - item.distance));
+
+        Ok(collapse_best_per_memory(hits, limit as usize))
+    }
+
+    async fn delete_point(&self, memory_id: &str) -> Result<()> {
+        let mut points = self.points.lock().await;
+        points.retain(|p| p.memory_id != memory_id);
+        self.persist(&points).await
+    }
+
+    async fn contains(&self, memory_id: &str) -> Result<bool> {
+        let points = self.points.lock().await;
+        Ok(points.iter().any(|p| p.memory_id == memory_id))
+    }
+}
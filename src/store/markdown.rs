@@ -1,15 +1,27 @@
-use std::path::Path;
 use anyhow::Result;
 use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 
+use crate::store::backend::Storage;
+use crate::store::oplog::{Hlc, OpLog};
 use crate::types::{IdentityCard, JournalEntry, Memory};
 
-/// Append a journal entry to today's file
-pub fn write_journal_entry(store_path: &Path, entry: &JournalEntry) -> Result<()> {
+/// Append a journal entry as its own immutable blob under
+/// `journal/<date>/<millis>-<nonce>.md`, rather than a single per-day blob
+/// that's fetched, concatenated onto, and rewritten — a read-modify-write
+/// that would drop one side's line whenever two writers race on the same
+/// day (two devices sharing an S3 backend, say). Keying each entry by its
+/// own timestamp instead mirrors how `OpLog::append` writes one immutable
+/// blob per op rather than rewriting a shared log file. `blob_put` still
+/// goes through the `Storage` backend — same reasoning as
+/// `read_identity`/`write_identity` — so the entry lands in S3/Garage when
+/// that backend is configured, and sealed under `EncryptingBackend` when
+/// `MEMORY_STORE_KEY` is set. `read_journal` reassembles a day's content by
+/// listing and concatenating these in order.
+pub async fn write_journal_entry(backend: &dyn Storage, entry: &JournalEntry) -> Result<()> {
     let date = entry.timestamp.format("%Y-%m-%d").to_string();
-    let dir = store_path.join("journal");
-    std::fs::create_dir_all(&dir)?;
-    let file = dir.join(format!("{}.md", date));
+    let nonce = uuid::Uuid::new_v4();
+    let key = format!("journal/{}/{:013}-{}.md", date, entry.timestamp.timestamp_millis(), nonce);
 
     let time = entry.timestamp.format("%H:%M").to_string();
     let gate = entry.gate.as_str();
@@ -17,84 +29,187 @@ pub fn write_journal_entry(store_path: &Path, entry: &JournalEntry) -> Result<()
         "\n## {} - {}\n[{}] {}\n",
         time, gate, gate, entry.content
     );
+    backend.blob_put(&key, line.into_bytes()).await
+}
 
-    use std::io::Write;
-    let mut f = std::fs::OpenOptions::new()
-        .create(true).append(true).open(&file)?;
-
-    // Write header if new file
-    if f.metadata()?.len() == 0 {
-        write!(f, "# {}\n", date)?;
+/// Read all journal entries for a given date, reassembled in chronological
+/// order from the individual blobs `write_journal_entry` writes.
+pub async fn read_journal(backend: &dyn Storage, date: &NaiveDate) -> Result<String> {
+    let prefix = format!("journal/{}/", date.format("%Y-%m-%d"));
+    let mut keys = backend.blob_list(&prefix).await?;
+    if keys.is_empty() {
+        return Ok(String::new());
     }
-    write!(f, "{}", line)?;
-    Ok(())
-}
+    keys.sort();
 
-/// Read all journal entries for a given date
-pub fn read_journal(store_path: &Path, date: &NaiveDate) -> Result<String> {
-    let file = store_path
-        .join("journal")
-        .join(format!("{}.md", date.format("%Y-%m-%d")));
-    if file.exists() {
-        Ok(std::fs::read_to_string(&file)?)
-    } else {
-        Ok(String::new())
+    let mut content = format!("# {}\n", date.format("%Y-%m-%d"));
+    for key in keys {
+        if let Some(bytes) = backend.blob_fetch(&key).await? {
+            content.push_str(&String::from_utf8_lossy(&bytes));
+        }
     }
+    Ok(content)
 }
 
-/// Write a long-term memory file with YAML frontmatter
-pub fn write_long_term(store_path: &Path, memory: &Memory) -> Result<()> {
+/// Write a long-term memory file with YAML frontmatter. Routed through
+/// `Storage` for the same reason as `write_journal_entry` — long-term facts
+/// are most of what a memory store holds, so `blob_put` seals them under
+/// `EncryptingBackend` the same way, instead of the bulk of a user's memory
+/// content being the one thing `MEMORY_STORE_KEY` doesn't actually cover.
+pub async fn write_long_term(backend: &dyn Storage, memory: &Memory) -> Result<()> {
     let category = category_for_gate(&memory.gate);
     let slug = slugify(&memory.id);
-    let dir = store_path.join("long-term").join(category);
-    std::fs::create_dir_all(&dir)?;
-    let file = dir.join(format!("{}.md", slug));
+    let key = format!("long-term/{}/{}.md", category, slug);
 
     let frontmatter = serde_yaml::to_string(&memory)?;
     let content = format!("---\n{}---\n\n{}\n", frontmatter, memory.content);
-    std::fs::write(&file, content)?;
-    Ok(())
+    backend.blob_put(&key, content.into_bytes()).await
+}
+
+/// Every memory recorded under `long-term/`, parsed back from each file's
+/// YAML frontmatter. Used by the store-reconciliation worker to find
+/// memories SQLite or the vector index have lost track of after a partial
+/// write.
+pub async fn list_long_term_memories(backend: &dyn Storage) -> Result<Vec<Memory>> {
+    let mut memories = Vec::new();
+    for key in backend.blob_list("long-term/").await? {
+        if !key.ends_with(".md") {
+            continue;
+        }
+        let Some(bytes) = backend.blob_fetch(&key).await? else { continue };
+        let raw = String::from_utf8_lossy(&bytes);
+        let Some(frontmatter) = raw.strip_prefix("---\n").and_then(|rest| rest.split_once("---\n")) else {
+            continue;
+        };
+        match serde_yaml::from_str::<Memory>(frontmatter.0) {
+            Ok(memory) => memories.push(memory),
+            Err(e) => tracing::warn!("skipping unparseable long-term entry {}: {}", key, e),
+        }
+    }
+    Ok(memories)
+}
+
+const IDENTITY_KEY: &str = "identity.entries.json";
+
+/// One paragraph of the identity card, tagged with the HLC timestamp (and,
+/// via the timestamp's `node_id`, the device) that wrote it. Storing the
+/// card as a set of these instead of one blob is what lets two sessions
+/// regenerate it concurrently without one clobbering the other — see
+/// `write_identity`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdentityEntry {
+    /// What this paragraph is a claim about (e.g. "alice" or "communication
+    /// style"), so a later paragraph about the same thing supersedes it
+    /// instead of sitting alongside it forever.
+    key: String,
+    timestamp: Hlc,
+    paragraph: String,
+}
+
+/// The claim key a paragraph is making: the text before its first colon
+/// (e.g. a "Alice:" or "Communication style:" heading), or the whole
+/// paragraph if it has none, so freeform lines never collide with anything.
+///
+/// Depends on `extract::IDENTITY_PROMPT` instructing the model to emit a
+/// stable "Topic: " heading per paragraph — without that contract every
+/// regenerated paragraph hashes to a fresh key, the union in
+/// `write_identity` never supersedes anything, and the card grows without
+/// bound. Keep the two in sync if either changes.
+fn claim_key(paragraph: &str) -> String {
+    match paragraph.find(':') {
+        Some(idx) => paragraph[..idx].trim().to_lowercase(),
+        None => paragraph.trim().to_lowercase(),
+    }
+}
+
+async fn load_identity_entries(backend: &dyn Storage) -> Result<Vec<IdentityEntry>> {
+    match backend.blob_fetch(IDENTITY_KEY).await? {
+        Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        None => Ok(Vec::new()),
+    }
 }
 
-/// Read identity card
-pub fn read_identity(store_path: &Path) -> Result<Option<IdentityCard>> {
-    let file = store_path.join("identity.md");
-    if !file.exists() {
+/// Read identity card. Goes through the `Storage` backend rather than the
+/// filesystem directly, so it works the same whether the store lives under
+/// `$HOME` or in an S3/Garage bucket. Reconstructs prose by rendering the
+/// merged entry set in timestamp order, oldest claim first.
+pub async fn read_identity(backend: &dyn Storage) -> Result<Option<IdentityCard>> {
+    let mut entries = load_identity_entries(backend).await?;
+    if entries.is_empty() {
         return Ok(None);
     }
-    let raw = std::fs::read_to_string(&file)?;
+    entries.sort_by_key(|e| e.timestamp);
+
+    let content = entries
+        .iter()
+        .map(|e| e.paragraph.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
     Ok(Some(IdentityCard {
         person: None,
         project: None,
-        content: raw,
+        content,
         last_updated: Utc::now(),
     }))
 }
 
-/// Write identity card
-pub fn write_identity(store_path: &Path, card: &IdentityCard) -> Result<()> {
-    let file = store_path.join("identity.md");
-    std::fs::write(&file, &card.content)?;
-    Ok(())
+/// Merge a freshly-regenerated identity card into the existing entry set
+/// instead of overwriting it. Each paragraph of `card.content` becomes an
+/// entry tagged with a new HLC timestamp; entries are then unioned by claim
+/// key, keeping whichever timestamp is newer per key. Two stores that each
+/// write independently and later sync their entries converge on the same
+/// content no matter which write lands first — the crash-prone case this
+/// replaces is a blind overwrite where two concurrent `reflect` calls could
+/// otherwise each discard the other's update.
+pub async fn write_identity(backend: &dyn Storage, oplog: &OpLog, card: &IdentityCard) -> Result<()> {
+    let existing = load_identity_entries(backend).await?;
+
+    let incoming: Vec<IdentityEntry> = card
+        .content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|paragraph| IdentityEntry {
+            key: claim_key(paragraph),
+            timestamp: oplog.next_timestamp(),
+            paragraph: paragraph.to_string(),
+        })
+        .collect();
+
+    let mut by_key: std::collections::HashMap<String, IdentityEntry> = std::collections::HashMap::new();
+    for entry in existing.into_iter().chain(incoming) {
+        by_key
+            .entry(entry.key.clone())
+            .and_modify(|current| {
+                if entry.timestamp > current.timestamp {
+                    *current = entry.clone();
+                }
+            })
+            .or_insert(entry);
+    }
+
+    let merged: Vec<IdentityEntry> = by_key.into_values().collect();
+    backend.blob_put(IDENTITY_KEY, serde_json::to_vec(&merged)?).await
 }
 
-/// Search all markdown files for a query string (basic grep)
-pub fn search_all(store_path: &Path, query: &str) -> Result<Vec<String>> {
+/// Search all markdown blobs (journal entries, long-term memories) for a
+/// query string (basic grep). Goes through `Storage::blob_list`/`blob_fetch`
+/// rather than walking `store_path` directly, so this still works — and
+/// still sees plaintext, not ciphertext — against an S3/Garage-backed or
+/// encrypted store.
+pub async fn search_all(backend: &dyn Storage, query: &str) -> Result<Vec<String>> {
     let mut results = Vec::new();
     let query_lower = query.to_lowercase();
 
-    for entry in walkdir::WalkDir::new(store_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+    for key in backend.blob_list("").await? {
+        if !key.ends_with(".md") {
             continue;
         }
-        if let Ok(content) = std::fs::read_to_string(path) {
-            if content.to_lowercase().contains(&query_lower) {
-                results.push(content);
-            }
+        let Some(bytes) = backend.blob_fetch(&key).await? else { continue };
+        let content = String::from_utf8_lossy(&bytes).into_owned();
+        if content.to_lowercase().contains(&query_lower) {
+            results.push(content);
         }
     }
     Ok(results)
@@ -1,20 +1,31 @@
+pub mod backend;
 pub mod markdown;
+pub mod index;
 pub mod sqlite;
+pub mod lmdb_tantivy;
+pub mod vector;
 pub mod embeddings;
+pub mod local_vectors;
 pub mod graph;
+pub mod oplog;
+pub mod pg;
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use anyhow::Result;
 
 pub struct Store {
     pub path: PathBuf,
-    pub db: sqlite::SqliteStore,
-    pub vectors: Option<embeddings::EmbeddingStore>,
+    pub backend: Arc<dyn backend::Storage>,
+    pub oplog: oplog::OpLog,
+    pub db: Box<dyn index::MemoryIndex>,
+    pub vectors: Box<dyn vector::VectorBackend>,
     pub graph: Option<graph::GraphStore>,
+    pub pg: Option<pg::PgStore>,
 }
 
 impl Store {
-    pub async fn init(path: &Path) -> Result<Self> {
+    pub async fn init(path: &Path, metrics: Option<&crate::otel::Metrics>) -> Result<Self> {
         // Ensure directory structure exists
         let dirs = [
             "journal", "digests", "summaries", "themes",
@@ -26,17 +37,49 @@ impl Store {
             std::fs::create_dir_all(path.join(dir))?;
         }
 
-        let db = sqlite::SqliteStore::open(path)?;
+        let backend = backend::connect(path, metrics).await?;
+        let oplog = oplog::OpLog::new(backend.clone()).await?;
 
-        // Try to connect to Qdrant (optional, degrades gracefully)
-        let vectors = match embeddings::EmbeddingStore::connect().await {
+        // The index backend is picked once at open time: SQLite+FTS5 by
+        // default, or a pure-Rust LMDB+Tantivy index for embedded
+        // environments that can't carry FTS5's compile-time dependency.
+        // Both implement `MemoryIndex`, so nothing downstream cares which
+        // one is live.
+        let db: Box<dyn index::MemoryIndex> = match std::env::var("MEMORY_INDEX_BACKEND").as_deref() {
+            Ok("lmdb") => {
+                tracing::info!("memory index backend: lmdb+tantivy");
+                Box::new(lmdb_tantivy::LmdbTantivyIndex::open(path)?)
+            }
+            _ => {
+                tracing::info!("memory index backend: sqlite+fts5");
+                Box::new(sqlite::SqliteStore::open(path)?)
+            }
+        };
+
+        // Rebuild the index from the op log on every startup, so memories
+        // logged before a crash mid-write are reindexed; safe to run
+        // unconditionally since `rebuild`'s replay is idempotent. Only the
+        // memory index is rebuilt: the vector backend and graph store are
+        // not, and index rows for memories the log has deleted stay.
+        match oplog.rebuild(db.as_ref()).await {
+            Ok(reindexed) if reindexed > 0 => {
+                tracing::info!("oplog replay: reconciled {} memories into the index", reindexed);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("oplog replay failed, starting from index state as-is: {}", e),
+        }
+
+        // Vector search always has somewhere to live: Qdrant Cloud when
+        // configured, otherwise a local HNSW index file next to `index.db`
+        // so semantic recall works with zero external services.
+        let vectors: Box<dyn vector::VectorBackend> = match embeddings::EmbeddingStore::connect().await {
             Ok(v) => {
                 tracing::info!("qdrant cloud connected");
-                Some(v)
+                Box::new(v)
             }
             Err(e) => {
-                tracing::warn!("qdrant unavailable, vector search disabled: {}", e);
-                None
+                tracing::warn!("qdrant unavailable, falling back to local vector index: {}", e);
+                Box::new(local_vectors::LocalVectorStore::open(path).await?)
             }
         };
 
@@ -52,11 +95,36 @@ impl Store {
             }
         };
 
+        // Pluggable Postgres repository, for multi-user/server deployments
+        // that would rather point at a managed database than operate
+        // markdown files plus Neo4j. Opt-in only: most setups don't set
+        // MEMORY_PG_ENABLED, so this silently stays off instead of warning.
+        // Its own env var, separate from MEMORY_BACKEND (which only picks
+        // the blob store between "fs" and "s3") — the two compose, so
+        // MEMORY_BACKEND=s3 plus MEMORY_PG_ENABLED=1 is a valid setup.
+        let pg = if std::env::var("MEMORY_PG_ENABLED").as_deref() == Ok("1") {
+            match pg::PgStore::connect().await {
+                Ok(p) => {
+                    tracing::info!("postgres repository connected");
+                    Some(p)
+                }
+                Err(e) => {
+                    tracing::warn!("postgres unavailable: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Ok(Self {
             path: path.to_path_buf(),
+            backend,
+            oplog,
             db,
             vectors,
             graph,
+            pg,
         })
     }
 }
@@ -0,0 +1,243 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::store::backend::Storage;
+use crate::store::index::MemoryIndex;
+use crate::types::Memory;
+
+/// Every `KEEP_STATE_EVERY` ops, fold the log into a fresh checkpoint so old
+/// ops can be garbage-collected instead of replayed from the beginning.
+pub const KEEP_STATE_EVERY: u64 = 64;
+
+/// A hybrid logical clock: wall-clock millis, tie-broken by a per-node
+/// counter and node id. Two nodes can never produce the same timestamp, and
+/// timestamps across nodes still sort close to real time, which is what
+/// gives the op log a total, deterministic order without a central clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Hlc {
+    pub millis: u64,
+    pub counter: u32,
+    pub node_id: u32,
+}
+
+impl Hlc {
+    fn key(&self) -> String {
+        format!("{:020}-{:010}-{:010}", self.millis, self.counter, self.node_id)
+    }
+}
+
+impl std::fmt::Display for Hlc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// One mutation to the memory set. Every write path (`remember`, `forget`,
+/// access bumps, graph edges, journal archival) has a corresponding variant,
+/// each actually constructed at its call site (`recall`'s access bump,
+/// `remember`'s `auto_link` edges), so the full history can be replayed
+/// deterministically. There's deliberately no `UpdateConfidence` variant:
+/// confidence only ever changes as part of a whole new `Memory` (consolidation's
+/// merge, `remember`'s overwrite), which already round-trips through
+/// `AddMemory` — a bare confidence-only op would have no caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Op {
+    AddMemory(Memory),
+    BumpAccess { id: String },
+    DeleteMemory { id: String },
+    AddEdge { from_id: String, to_id: String, relation: String },
+    ArchiveJournal { date: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpRecord {
+    timestamp: Hlc,
+    payload: Op,
+}
+
+/// Folded state: the set of live memories, keyed by id. This is what a
+/// checkpoint captures and what `Op` replay converges to regardless of
+/// which node applied which op first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct State {
+    pub memories: std::collections::BTreeMap<String, Memory>,
+}
+
+impl State {
+    fn apply(&mut self, op: &Op, at: Hlc) {
+        match op {
+            Op::AddMemory(m) => {
+                self.memories.insert(m.id.clone(), m.clone());
+            }
+            Op::BumpAccess { id } => {
+                if let Some(m) = self.memories.get_mut(id) {
+                    m.access_count += 1;
+                    // The op's own timestamp, not the replay time, so every
+                    // replay of the same ops yields the same `last_accessed`.
+                    m.last_accessed = chrono::TimeZone::timestamp_millis_opt(&chrono::Utc, at.millis as i64)
+                        .single()
+                        .unwrap_or(m.last_accessed);
+                }
+            }
+            Op::DeleteMemory { id } => {
+                self.memories.remove(id);
+            }
+            Op::AddEdge { .. } => {
+                // Edges live in GraphStore, not in the folded memory set;
+                // the op is logged as a record of the edge, but replay does
+                // not create the edge in any graph store.
+            }
+            Op::ArchiveJournal { .. } => {
+                // Journal archival doesn't change the memory set either —
+                // logged for completeness/audit of what happened and when.
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Checkpoint {
+    timestamp: Hlc,
+    state: State,
+}
+
+/// Append-only operation log over a `Storage` backend. Ops are immutable
+/// blobs keyed by their HLC timestamp under `oplog/ops/`, checkpoints under
+/// `oplog/checkpoints/`, which works identically over the local filesystem
+/// backend or a remote object-store one.
+pub struct OpLog {
+    backend: std::sync::Arc<dyn Storage>,
+    node_id: u32,
+    counter: std::sync::atomic::AtomicU32,
+    ops_since_checkpoint: std::sync::atomic::AtomicU64,
+}
+
+impl OpLog {
+    /// `std::process::id()` used to stand in for this, but low, frequently
+    /// reused PIDs (containers routinely assign PID 1) make cross-machine
+    /// collisions plausible — and a node_id collision plus a millis/counter
+    /// tie breaks the "two nodes can never produce the same timestamp"
+    /// guarantee `Hlc` relies on. So the id is a UUID, generated once and
+    /// persisted under this row key, read back on every subsequent start.
+    const NODE_ID_ROW: &'static str = "node_id";
+
+    pub async fn new(backend: std::sync::Arc<dyn Storage>) -> Result<Self> {
+        let node_id = Self::load_or_create_node_id(&backend).await?;
+        Ok(Self {
+            backend,
+            node_id,
+            counter: std::sync::atomic::AtomicU32::new(0),
+            ops_since_checkpoint: std::sync::atomic::AtomicU64::new(0),
+        })
+    }
+
+    async fn load_or_create_node_id(backend: &std::sync::Arc<dyn Storage>) -> Result<u32> {
+        if let Some(bytes) = backend.row_get("meta", Self::NODE_ID_ROW).await? {
+            if let Ok(uuid) = uuid::Uuid::parse_str(&String::from_utf8_lossy(&bytes)) {
+                return Ok(u32::from_le_bytes(uuid.as_bytes()[..4].try_into().unwrap()));
+            }
+        }
+        let uuid = uuid::Uuid::new_v4();
+        backend.row_put("meta", Self::NODE_ID_ROW, uuid.to_string().into_bytes()).await?;
+        Ok(u32::from_le_bytes(uuid.as_bytes()[..4].try_into().unwrap()))
+    }
+
+    /// Mint a fresh HLC timestamp without appending an op. Also used by
+    /// callers (like the identity card's CRDT merge) that need to tag their
+    /// own conflict-free state with the same clock the op log uses, without
+    /// going through `append`.
+    pub(crate) fn next_timestamp(&self) -> Hlc {
+        let millis = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        let counter = self.counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Hlc { millis, counter, node_id: self.node_id }
+    }
+
+    /// Append an op, then checkpoint if `KEEP_STATE_EVERY` ops have
+    /// accumulated since the last one.
+    pub async fn append(&self, payload: Op) -> Result<Hlc> {
+        let timestamp = self.next_timestamp();
+        let record = OpRecord { timestamp, payload };
+        let key = format!("oplog/ops/{}.json", timestamp.key());
+        self.backend.blob_put(key.as_str(), serde_json::to_vec(&record)?).await?;
+
+        let count = self.ops_since_checkpoint.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        if count >= KEEP_STATE_EVERY {
+            self.checkpoint().await?;
+            self.ops_since_checkpoint.store(0, std::sync::atomic::Ordering::SeqCst);
+        }
+        Ok(timestamp)
+    }
+
+    /// Reconstruct state: load the most recent checkpoint, then fold every
+    /// op with `timestamp > checkpoint.timestamp` in sorted order. Because
+    /// ops apply in a fixed total order, any replica that has seen the same
+    /// set of ops converges to the same `State`.
+    pub async fn load_state(&self) -> Result<State> {
+        let (mut state, since) = match self.latest_checkpoint().await? {
+            Some(cp) => (cp.state, Some(cp.timestamp)),
+            None => (State::default(), None),
+        };
+
+        let mut keys = self.backend.blob_list("oplog/ops/").await?;
+        keys.sort();
+
+        for key in keys {
+            let Some(bytes) = self.backend.blob_fetch(&key).await? else { continue };
+            let record: OpRecord = serde_json::from_slice(&bytes)?;
+            if let Some(since) = since {
+                if record.timestamp <= since {
+                    continue;
+                }
+            }
+            state.apply(&record.payload, record.timestamp);
+        }
+
+        Ok(state)
+    }
+
+    /// Reconstruct `memories`/`memories_fts` in `db` from the op log alone,
+    /// for when FTS indexes corrupt or the markdown files and SQLite have
+    /// drifted apart. `load_state` already does the "checkpoint + replay
+    /// the suffix of ops newer than it" folding — `index_memory` is an
+    /// upsert, so this is safe to run against a SQLite db that's already
+    /// partially (or even fully) consistent. Memories present in `db` but
+    /// absent from the folded state are left in place, and nothing outside
+    /// `db` (vector backend, graph store) is rebuilt.
+    ///
+    /// Invariant this whole log design relies on: folding a checkpoint with
+    /// every op timestamped strictly after it reproduces the same `State`
+    /// regardless of how those ops are batched or re-ordered within ties,
+    /// because `State::apply` is commutative on distinct ids and each op
+    /// keyed by memory id is idempotent to re-apply. That's what makes
+    /// `rebuild` safe to call at any time, not just after a crash.
+    pub async fn rebuild(&self, db: &dyn MemoryIndex) -> Result<u64> {
+        let state = self.load_state().await?;
+        let mut reindexed = 0u64;
+        for memory in state.memories.values() {
+            db.index_memory(memory)?;
+            reindexed += 1;
+        }
+        Ok(reindexed)
+    }
+
+    async fn latest_checkpoint(&self) -> Result<Option<Checkpoint>> {
+        let mut keys = self.backend.blob_list("oplog/checkpoints/").await?;
+        keys.sort();
+        let Some(latest) = keys.pop() else { return Ok(None) };
+        let Some(bytes) = self.backend.blob_fetch(&latest).await? else { return Ok(None) };
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// Fold the full log into a new checkpoint blob. Older ops are left in
+    /// place (deletion/GC is a separate, explicit maintenance step) but are
+    /// no longer needed for `load_state` to be correct.
+    async fn checkpoint(&self) -> Result<()> {
+        let state = self.load_state().await?;
+        let timestamp = self.next_timestamp();
+        let checkpoint = Checkpoint { timestamp, state };
+        let key = format!("oplog/checkpoints/{}.json", timestamp.key());
+        self.backend.blob_put(&key, serde_json::to_vec(&checkpoint)?).await?;
+        tracing::info!("oplog checkpoint written at {}", timestamp);
+        Ok(())
+    }
+}
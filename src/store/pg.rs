@@ -0,0 +1,312 @@
+use anyhow::Result;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Row};
+
+use crate::types::{DecayClass, Gate, Memory};
+
+/// Embedded schema, applied in order on every `connect()`. Each statement is
+/// `IF NOT EXISTS` so re-running against an already-migrated database is a
+/// no-op; a real version-tracked migration ladder (see `SqliteStore`'s) is
+/// future work once this backend needs to evolve its schema independently.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS memories (
+        id TEXT PRIMARY KEY,
+        created TIMESTAMPTZ NOT NULL,
+        gate TEXT NOT NULL,
+        person TEXT,
+        project TEXT,
+        confidence DOUBLE PRECISION NOT NULL,
+        last_accessed TIMESTAMPTZ NOT NULL,
+        access_count INTEGER NOT NULL DEFAULT 1,
+        decay_class TEXT NOT NULL,
+        content TEXT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS memories_person_idx ON memories (person)",
+    "CREATE INDEX IF NOT EXISTS memories_project_idx ON memories (project)",
+    "CREATE INDEX IF NOT EXISTS memories_content_fts_idx
+        ON memories USING GIN (to_tsvector('english', content))",
+    "CREATE TABLE IF NOT EXISTS edges (
+        from_id TEXT NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+        to_id TEXT NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+        relation TEXT NOT NULL,
+        created TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (from_id, to_id, relation)
+    )",
+];
+
+/// A Postgres-backed alternative to the markdown-file store plus Neo4j,
+/// for multi-user/server deployments where operating two separate stores
+/// per user is awkward. Selected with `MEMORY_PG_ENABLED=1` — a dedicated
+/// flag, independent of `MEMORY_BACKEND` (which only picks the blob store
+/// between local filesystem and S3), so the two compose instead of
+/// fighting over the same env var. Writes go here alongside the local
+/// index/graph (see `do_remember`/`do_forget`), and `do_recall`/
+/// `do_query_graph` read back through `search_fts`/`find_related_to_anchor`
+/// so postgres is a real, query-able backend and not just a write-side
+/// shadow copy.
+pub struct PgStore {
+    pool: PgPool,
+}
+
+impl PgStore {
+    pub async fn connect() -> Result<Self> {
+        let url = std::env::var("DATABASE_URL")
+            .map_err(|_| anyhow::anyhow!("DATABASE_URL not set"))?;
+
+        let pool = PgPoolOptions::new()
+            .max_connections(
+                std::env::var("PG_MAX_CONNECTIONS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+            )
+            .connect(&url)
+            .await?;
+
+        for migration in MIGRATIONS {
+            sqlx::query(migration).execute(&pool).await?;
+        }
+
+        Ok(Self { pool })
+    }
+
+    pub async fn index_memory(&self, memory: &Memory) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO memories \
+             (id, created, gate, person, project, confidence, \
+              last_accessed, access_count, decay_class, content) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+             ON CONFLICT (id) DO UPDATE SET \
+               confidence = EXCLUDED.confidence, \
+               last_accessed = EXCLUDED.last_accessed, \
+               access_count = EXCLUDED.access_count, \
+               content = EXCLUDED.content",
+        )
+        .bind(&memory.id)
+        .bind(memory.created)
+        .bind(memory.gate.as_str())
+        .bind(&memory.person)
+        .bind(&memory.project)
+        .bind(memory.confidence)
+        .bind(memory.last_accessed)
+        .bind(memory.access_count as i32)
+        .bind(serde_json::to_string(&memory.decay_class)?)
+        .bind(&memory.content)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Full-text search over `content` via Postgres's built-in `tsvector`,
+    /// the same job `memories_fts` does for the SQLite backend.
+    pub async fn search_fts(&self, query: &str, limit: i64) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            "SELECT id, created, gate, person, project, confidence, \
+                    last_accessed, access_count, decay_class, content \
+             FROM memories \
+             WHERE to_tsvector('english', content) @@ plainto_tsquery('english', $1) \
+             ORDER BY ts_rank(to_tsvector('english', content), plainto_tsquery('english', $1)) DESC \
+             LIMIT $2",
+        )
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_memory).collect()
+    }
+
+    pub async fn touch_memory(&self, id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE memories SET access_count = access_count + 1, last_accessed = now() \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_memory(&self, id: &str) -> Result<Option<Memory>> {
+        let row = sqlx::query(
+            "SELECT id, created, gate, person, project, confidence, \
+                    last_accessed, access_count, decay_class, content \
+             FROM memories WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else { return Ok(None) };
+        let memory = row_to_memory(&row)?;
+        sqlx::query("DELETE FROM memories WHERE id = $1").bind(id).execute(&self.pool).await?;
+        Ok(Some(memory))
+    }
+
+    pub async fn add_edge(&self, from_id: &str, to_id: &str, relation: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO edges (from_id, to_id, relation) VALUES ($1, $2, $3) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(from_id)
+        .bind(to_id)
+        .bind(relation)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// `find_related`'s Neo4j traversal, reimplemented as a recursive CTE:
+    /// walk `edges` up to `depth` hops from `memory_id` in either direction.
+    pub async fn find_related(
+        &self,
+        memory_id: &str,
+        depth: i32,
+    ) -> Result<Vec<(String, String, String)>> {
+        let rows = sqlx::query(
+            "WITH RECURSIVE walk AS ( \
+                SELECT from_id, to_id, relation, 1 AS hop \
+                FROM edges WHERE from_id = $1 OR to_id = $1 \
+                UNION ALL \
+                SELECT e.from_id, e.to_id, e.relation, w.hop + 1 \
+                FROM edges e \
+                JOIN walk w ON e.from_id IN (w.from_id, w.to_id) OR e.to_id IN (w.from_id, w.to_id) \
+                WHERE w.hop < $2 \
+             ) \
+             SELECT DISTINCT m.id, m.content, w.relation \
+             FROM walk w \
+             JOIN memories m ON m.id = w.to_id OR m.id = w.from_id \
+             WHERE m.id <> $1 \
+             LIMIT 10",
+        )
+        .bind(memory_id)
+        .bind(depth)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let id: String = row.try_get("id")?;
+            let preview: String = row.try_get("content")?;
+            let relation: String = row.try_get("relation")?;
+            out.push((id, relation, preview));
+        }
+        Ok(out)
+    }
+
+    /// `find_related_to_anchor`'s Neo4j traversal, reimplemented as a
+    /// recursive CTE: seed `walk` from every memory matching `person`/
+    /// `project` instead of a single starting id, then walk `edges` up to
+    /// `depth` hops same as `find_related`. Backs `do_query_graph` when
+    /// Neo4j isn't connected but `MEMORY_PG_ENABLED=1` is.
+    pub async fn find_related_to_anchor(
+        &self,
+        person: Option<&str>,
+        project: Option<&str>,
+        depth: i32,
+    ) -> Result<Vec<(String, String, String)>> {
+        if person.is_none() && project.is_none() {
+            anyhow::bail!("find_related_to_anchor needs a person or project anchor");
+        }
+
+        let rows = sqlx::query(
+            "WITH RECURSIVE anchors AS ( \
+                SELECT id FROM memories \
+                WHERE ($1::text IS NOT NULL AND person = $1) \
+                   OR ($2::text IS NOT NULL AND project = $2) \
+             ), \
+             walk AS ( \
+                SELECT e.from_id, e.to_id, e.relation, 1 AS hop \
+                FROM edges e \
+                JOIN anchors a ON e.from_id = a.id OR e.to_id = a.id \
+                UNION ALL \
+                SELECT e.from_id, e.to_id, e.relation, w.hop + 1 \
+                FROM edges e \
+                JOIN walk w ON e.from_id IN (w.from_id, w.to_id) OR e.to_id IN (w.from_id, w.to_id) \
+                WHERE w.hop < $3 \
+             ) \
+             SELECT DISTINCT m.id, m.content, w.relation \
+             FROM walk w \
+             JOIN memories m ON m.id = w.to_id OR m.id = w.from_id \
+             WHERE m.id NOT IN (SELECT id FROM anchors) \
+             LIMIT 20",
+        )
+        .bind(person)
+        .bind(project)
+        .bind(depth)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let id: String = row.try_get("id")?;
+            let preview: String = row.try_get("content")?;
+            let relation: String = row.try_get("relation")?;
+            out.push((id, relation, preview));
+        }
+        Ok(out)
+    }
+
+    /// Links `memory_id` to every other memory sharing its person/project,
+    /// returning the ids it linked to so the caller can log an
+    /// `Op::AddEdge` per edge — see `GraphStore::auto_link`'s doc comment
+    /// for why that matters.
+    pub async fn auto_link(
+        &self,
+        memory_id: &str,
+        person: Option<&str>,
+        project: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let mut linked = Vec::new();
+        if let Some(person) = person.filter(|p| !p.is_empty()) {
+            let rows = sqlx::query(
+                "INSERT INTO edges (from_id, to_id, relation) \
+                 SELECT $1, id, 'related_to' FROM memories \
+                 WHERE person = $2 AND id <> $1 \
+                 ON CONFLICT DO NOTHING \
+                 RETURNING to_id",
+            )
+            .bind(memory_id)
+            .bind(person)
+            .fetch_all(&self.pool)
+            .await?;
+            for row in rows {
+                linked.push(row.try_get("to_id")?);
+            }
+        }
+        if let Some(project) = project.filter(|p| !p.is_empty()) {
+            let rows = sqlx::query(
+                "INSERT INTO edges (from_id, to_id, relation) \
+                 SELECT $1, id, 'related_to' FROM memories \
+                 WHERE project = $2 AND id <> $1 \
+                 ON CONFLICT DO NOTHING \
+                 RETURNING to_id",
+            )
+            .bind(memory_id)
+            .bind(project)
+            .fetch_all(&self.pool)
+            .await?;
+            for row in rows {
+                linked.push(row.try_get("to_id")?);
+            }
+        }
+        Ok(linked)
+    }
+}
+
+fn row_to_memory(row: &sqlx::postgres::PgRow) -> Result<Memory> {
+    let decay_class: String = row.try_get("decay_class")?;
+    Ok(Memory {
+        id: row.try_get("id")?,
+        created: row.try_get("created")?,
+        gate: Gate::from_str(row.try_get::<String, _>("gate")?.as_str())
+            .unwrap_or(Gate::Epistemic),
+        person: row.try_get("person")?,
+        project: row.try_get("project")?,
+        confidence: row.try_get("confidence")?,
+        last_accessed: row.try_get("last_accessed")?,
+        access_count: row.try_get::<i32, _>("access_count")? as u32,
+        decay_class: serde_json::from_str(&decay_class).unwrap_or(DecayClass::Moderate),
+        content: row.try_get("content")?,
+    })
+}
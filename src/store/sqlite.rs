@@ -1,62 +1,145 @@
 use std::path::Path;
+use std::sync::Mutex;
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
+use crate::store::index::MemoryIndex;
 use crate::types::Memory;
 
+/// `rusqlite::Connection` is `Send` but not `Sync` (its statement cache is a
+/// `RefCell`), while `MemoryIndex` requires both — wrap it the same way
+/// `lmdb_tantivy.rs` wraps its `IndexWriter`, taking the lock per call
+/// rather than dropping the bound, since nothing here is hot enough to make
+/// the lock contention matter.
 pub struct SqliteStore {
-    conn: Connection,
+    conn: Mutex<Connection>,
+}
+
+/// Ordered schema migrations, applied in order starting just above whatever
+/// `PRAGMA user_version` the database already reports. Each closure must be
+/// safe to run inside a transaction and, ideally, idempotent (`IF NOT
+/// EXISTS`/`IF EXISTS`) so replaying against a database that already has a
+/// later migration's effects (e.g. from a crash between the DDL and the
+/// version bump) doesn't fail.
+type Migration = fn(&Connection) -> rusqlite::Result<()>;
+
+const MIGRATIONS: &[Migration] = &[migrate_v1_initial_schema, migrate_v2_redirects];
+
+/// Databases older than this can't be upgraded directly — an operator would
+/// need to run an intermediate version of the binary first. Bump this only
+/// when a migration actually requires a stepping stone to apply safely.
+const MIN_SUPPORTED_SCHEMA_VERSION: i64 = 1;
+
+fn migrate_v1_initial_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("
+        CREATE TABLE IF NOT EXISTS memories (
+            id TEXT PRIMARY KEY,
+            created TEXT NOT NULL,
+            gate TEXT NOT NULL,
+            person TEXT,
+            project TEXT,
+            confidence REAL NOT NULL,
+            last_accessed TEXT NOT NULL,
+            access_count INTEGER NOT NULL DEFAULT 1,
+            decay_class TEXT NOT NULL,
+            content TEXT NOT NULL,
+            file_path TEXT,
+            category TEXT
+        );
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
+            content, person, project,
+            content='memories', content_rowid='rowid'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+            INSERT INTO memories_fts(rowid, content, person, project)
+            VALUES (new.rowid, new.content, new.person, new.project);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, content, person, project)
+            VALUES ('delete', old.rowid, old.content, old.person, old.project);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, content, person, project)
+            VALUES ('delete', old.rowid, old.content, old.person, old.project);
+            INSERT INTO memories_fts(rowid, content, person, project)
+            VALUES (new.rowid, new.content, new.person, new.project);
+        END;
+    ")
+}
+
+/// Tracks memories consolidation has merged away: `duplicate_id` no longer
+/// has a row in `memories`, but anything still holding that id (a stale
+/// graph edge, a recall result cached before the merge) can look it up here
+/// and land on the surviving `canonical_id` instead of a dead end.
+fn migrate_v2_redirects(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("
+        CREATE TABLE IF NOT EXISTS redirects (
+            duplicate_id TEXT PRIMARY KEY,
+            canonical_id TEXT NOT NULL
+        );
+    ")
+}
+
+/// Apply every migration whose index exceeds the database's current
+/// `PRAGMA user_version`, each inside its own transaction, bumping the
+/// version as soon as it succeeds. Refuses to open a database stamped with
+/// a version newer than this binary understands (it would otherwise read a
+/// schema it doesn't know how to query) or one stamped older than
+/// `MIN_SUPPORTED_SCHEMA_VERSION` (it would need an intermediate migration
+/// this binary no longer carries).
+fn run_migrations(conn: &Connection) -> Result<()> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let target = MIGRATIONS.len() as i64;
+
+    if version > target {
+        anyhow::bail!(
+            "index.db schema version {} is newer than this binary understands (up to {}); \
+             refusing to open it to avoid corrupting it. Upgrade claude-memory first.",
+            version, target,
+        );
+    }
+    if version > 0 && version < MIN_SUPPORTED_SCHEMA_VERSION {
+        anyhow::bail!(
+            "index.db schema version {} is too old to upgrade directly (minimum supported is {}); \
+             run an intermediate version of claude-memory first.",
+            version, MIN_SUPPORTED_SCHEMA_VERSION,
+        );
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate() {
+        let migration_version = (i + 1) as i64;
+        if migration_version <= version {
+            continue;
+        }
+
+        conn.execute_batch("BEGIN")?;
+        if let Err(e) = migration(conn) {
+            conn.execute_batch("ROLLBACK").ok();
+            return Err(e.into());
+        }
+        conn.execute_batch(&format!("PRAGMA user_version = {migration_version}; COMMIT;"))?;
+        tracing::info!("applied index.db migration {}", migration_version);
+    }
+
+    Ok(())
 }
 
 impl SqliteStore {
     pub fn open(store_path: &Path) -> Result<Self> {
         let db_path = store_path.join("index.db");
         let conn = Connection::open(&db_path)?;
-
-        conn.execute_batch("
-            CREATE TABLE IF NOT EXISTS memories (
-                id TEXT PRIMARY KEY,
-                created TEXT NOT NULL,
-                gate TEXT NOT NULL,
-                person TEXT,
-                project TEXT,
-                confidence REAL NOT NULL,
-                last_accessed TEXT NOT NULL,
-                access_count INTEGER NOT NULL DEFAULT 1,
-                decay_class TEXT NOT NULL,
-                content TEXT NOT NULL,
-                file_path TEXT,
-                category TEXT
-            );
-
-            CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
-                content, person, project,
-                content='memories', content_rowid='rowid'
-            );
-
-            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
-                INSERT INTO memories_fts(rowid, content, person, project)
-                VALUES (new.rowid, new.content, new.person, new.project);
-            END;
-
-            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, content, person, project)
-                VALUES ('delete', old.rowid, old.content, old.person, old.project);
-            END;
-
-            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, content, person, project)
-                VALUES ('delete', old.rowid, old.content, old.person, old.project);
-                INSERT INTO memories_fts(rowid, content, person, project)
-                VALUES (new.rowid, new.content, new.person, new.project);
-            END;
-        ")?;
-
-        Ok(Self { conn })
+        run_migrations(&conn)?;
+        Ok(Self { conn: Mutex::new(conn) })
     }
+}
 
-    pub fn index_memory(&self, memory: &Memory) -> Result<()> {
-        self.conn.execute(
+impl MemoryIndex for SqliteStore {
+    fn index_memory(&self, memory: &Memory) -> Result<()> {
+        self.conn.lock().unwrap().execute(
             "INSERT OR REPLACE INTO memories \
              (id, created, gate, person, project, confidence, \
               last_accessed, access_count, decay_class, content) \
@@ -77,8 +160,9 @@ impl SqliteStore {
         Ok(())
     }
 
-    pub fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<Memory>> {
-        let mut stmt = self.conn.prepare(
+    fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<Memory>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
             "SELECT m.id, m.created, m.gate, m.person, m.project, \
                     m.confidence, m.last_accessed, m.access_count, \
                     m.decay_class, m.content \
@@ -111,8 +195,40 @@ impl SqliteStore {
         Ok(results)
     }
 
-    pub fn touch_memory(&self, id: &str) -> Result<()> {
-        self.conn.execute(
+    /// Every memory currently indexed. Used by the decay sweep, which needs
+    /// to score the whole set rather than a search-ranked subset.
+    fn list_all(&self) -> Result<Vec<Memory>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, created, gate, person, project, confidence, \
+                    last_accessed, access_count, decay_class, content \
+             FROM memories"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(Memory {
+                id: row.get(0)?,
+                created: parse_dt(row.get::<_, String>(1)?),
+                gate: parse_gate(row.get::<_, String>(2)?),
+                person: row.get(3)?,
+                project: row.get(4)?,
+                confidence: row.get(5)?,
+                last_accessed: parse_dt(row.get::<_, String>(6)?),
+                access_count: row.get(7)?,
+                decay_class: parse_decay(row.get::<_, String>(8)?),
+                content: row.get(9)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    fn touch_memory(&self, id: &str) -> Result<()> {
+        self.conn.lock().unwrap().execute(
             "UPDATE memories SET access_count = access_count + 1, \
              last_accessed = ?1 WHERE id = ?2",
             rusqlite::params![chrono::Utc::now().to_rfc3339(), id],
@@ -120,10 +236,10 @@ impl SqliteStore {
         Ok(())
     }
 
-    pub fn delete_memory(&self, id: &str) -> Result<Option<Memory>> {
+    fn delete_memory(&self, id: &str) -> Result<Option<Memory>> {
         let mem = self.get_memory(id)?;
         if mem.is_some() {
-            self.conn.execute(
+            self.conn.lock().unwrap().execute(
                 "DELETE FROM memories WHERE id = ?1",
                 rusqlite::params![id],
             )?;
@@ -132,7 +248,8 @@ impl SqliteStore {
     }
 
     fn get_memory(&self, id: &str) -> Result<Option<Memory>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
             "SELECT id, created, gate, person, project, confidence, \
                     last_accessed, access_count, decay_class, content \
              FROM memories WHERE id = ?1"
@@ -158,6 +275,25 @@ impl SqliteStore {
             _ => Ok(None),
         }
     }
+
+    /// Record that consolidation merged `duplicate_id` into `canonical_id`.
+    fn add_redirect(&self, duplicate_id: &str, canonical_id: &str) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO redirects (duplicate_id, canonical_id) VALUES (?1, ?2)",
+            rusqlite::params![duplicate_id, canonical_id],
+        )?;
+        Ok(())
+    }
+
+    /// Where a merged-away memory id now lives, if consolidation ever
+    /// absorbed it into another memory.
+    fn resolve_redirect(&self, id: &str) -> Result<Option<String>> {
+        Ok(self.conn.lock().unwrap().query_row(
+            "SELECT canonical_id FROM redirects WHERE duplicate_id = ?1",
+            rusqlite::params![id],
+            |row| row.get(0),
+        ).optional()?)
+    }
 }
 
 fn parse_dt(s: String) -> chrono::DateTime<chrono::Utc> {
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Semantic vector search, independent of where the index actually lives.
+/// `embed_and_store` chunks long content into overlapping windows so a
+/// single memory doesn't get averaged down into one diluted vector;
+/// `search_similar` collapses chunk-level matches back to one result per
+/// `memory_id`, keeping the best-scoring chunk. Implementations must be
+/// safe to share across tasks.
+#[async_trait]
+pub trait VectorBackend: Send + Sync {
+    async fn embed_and_store(
+        &self,
+        memory_id: &str,
+        content: &str,
+        person: Option<&str>,
+        project: Option<&str>,
+    ) -> Result<()>;
+
+    async fn search_similar(&self, query: &str, limit: u64) -> Result<Vec<(String, f32)>>;
+
+    async fn delete_point(&self, memory_id: &str) -> Result<()>;
+
+    /// Whether `memory_id` has at least one point indexed. Lets the
+    /// reconcile worker tell "indexed in SQLite but `embed_and_store`
+    /// swallowed a failure and never made it into the vector backend" apart
+    /// from "already fully indexed", which `db.get_memory` alone can't.
+    async fn contains(&self, memory_id: &str) -> Result<bool>;
+}
+
+pub(crate) const CHUNK_WORDS: usize = 256;
+pub(crate) const CHUNK_OVERLAP_WORDS: usize = 50;
+
+/// Split `content` into overlapping windows of roughly `CHUNK_WORDS` words
+/// (a rough stand-in for tokens) with `CHUNK_OVERLAP_WORDS` of overlap,
+/// preferring to break on sentence/paragraph boundaries so a chunk doesn't
+/// cut a thought in half. Short content that already fits in one window is
+/// returned unchunked. Shared by every `VectorBackend` impl so chunking
+/// behaves identically regardless of which index actually stores the
+/// vectors.
+pub(crate) fn chunk_content(content: &str) -> Vec<String> {
+    let sentences = split_sentences(content);
+    if sentences.is_empty() {
+        return vec![content.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut window: Vec<&str> = Vec::new();
+    let mut window_words = 0usize;
+
+    let mut i = 0;
+    while i < sentences.len() {
+        let sentence = sentences[i];
+        let sentence_words = sentence.split_whitespace().count().max(1);
+
+        window.push(sentence);
+        window_words += sentence_words;
+        i += 1;
+
+        if window_words >= CHUNK_WORDS || i == sentences.len() {
+            chunks.push(window.join(" "));
+
+            // Slide back by roughly CHUNK_OVERLAP_WORDS worth of sentences
+            // so the next window overlaps instead of starting cold.
+            let mut overlap_words = 0usize;
+            let mut keep_from = window.len();
+            while keep_from > 0 && overlap_words < CHUNK_OVERLAP_WORDS {
+                keep_from -= 1;
+                overlap_words += window[keep_from].split_whitespace().count().max(1);
+            }
+            window = window[keep_from..].to_vec();
+            window_words = overlap_words;
+        }
+    }
+
+    if chunks.is_empty() {
+        chunks.push(content.to_string());
+    }
+    chunks
+}
+
+/// Rough sentence/paragraph splitter: break after `.`, `!`, `?`, or a blank
+/// line, keeping the delimiter attached to the sentence it ends.
+fn split_sentences(content: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let bytes = content.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        let at_boundary = matches!(b, b'.' | b'!' | b'?')
+            || (*b == b'\n' && bytes.get(i.wrapping_sub(1)) == Some(&b'\n'));
+        if at_boundary {
+            let end = i + 1;
+            let sentence = content[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+    }
+    let tail = content[start..].trim();
+    if !tail.is_empty() {
+        sentences.push(tail);
+    }
+    sentences
+}
+
+/// Collapse chunk-level `(memory_id, score)` hits down to the best-scoring
+/// chunk per memory, sort descending, and truncate to `limit`. Shared by
+/// every `VectorBackend` impl so over-fetch-and-collapse behaves
+/// identically regardless of which index produced the raw hits.
+pub(crate) fn collapse_best_per_memory(
+    hits: impl Iterator<Item = (String, f32)>,
+    limit: usize,
+) -> Vec<(String, f32)> {
+    let mut best: std::collections::HashMap<String, f32> = std::collections::HashMap::new();
+    for (memory_id, score) in hits {
+        best.entry(memory_id)
+            .and_modify(|existing| *existing = existing.max(score))
+            .or_insert(score);
+    }
+
+    let mut out: Vec<(String, f32)> = best.into_iter().collect();
+    out.sort_by(|a, b| b.1.total_cmp(&a.1));
+    out.truncate(limit);
+    out
+}
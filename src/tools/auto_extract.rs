@@ -4,7 +4,15 @@ use crate::extract;
 
 impl MemoryServer {
     pub async fn do_auto_extract(&self, transcript: &str) -> Result<String> {
-        let memories = extract::extract_memories(transcript, &self.api_key).await?;
+        let memories = match extract::extract_memories(transcript, &self.api_key).await {
+            Ok(m) => m,
+            Err(e) => {
+                if let Some(ref metrics) = self.metrics {
+                    metrics.api_error("extract_memories");
+                }
+                return Err(e);
+            }
+        };
 
         if memories.is_empty() {
             return Ok("No memories worth keeping from this transcript.".into());
@@ -18,7 +26,12 @@ impl MemoryServer {
                 mem.person.as_deref(),
                 mem.project.as_deref(),
             ).await {
-                Ok(msg) => saved.push(msg),
+                Ok(msg) => {
+                    if let Some(ref metrics) = self.metrics {
+                        metrics.memory_extracted(&mem.gate);
+                    }
+                    saved.push(msg);
+                }
                 Err(e) => {
                     tracing::warn!("auto-extract save failed: {}", e);
                 }
@@ -0,0 +1,163 @@
+use anyhow::Result;
+
+use crate::consolidation::merge::{self, DUPLICATE_SIMILARITY};
+use crate::server::MemoryServer;
+use crate::store::oplog::Op;
+use crate::types::Memory;
+
+impl MemoryServer {
+    /// On-demand sweep: fold near-duplicate memories (same `person`/
+    /// `project`, cosine similarity above `DUPLICATE_SIMILARITY`) together
+    /// with CRDT semantics instead of leaving them as separate rows that
+    /// double-count in recall. Mirrors `do_forget_sweep`'s
+    /// collect-then-act-per-candidate shape.
+    pub async fn do_consolidate(&self) -> Result<String> {
+        let store = self.store.lock().await;
+        let all = store.db.list_all()?;
+
+        let mut merged_away: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut merges = 0usize;
+
+        for memory in &all {
+            if merged_away.contains(&memory.id) {
+                continue;
+            }
+
+            // `current` is the running merge result for this memory's chain:
+            // after each merge we rescan similarity against the *merged*
+            // content/id rather than the stale pre-loop snapshot, so a
+            // memory with two qualifying duplicates folds both in instead
+            // of the second merge overwriting the first's contribution.
+            let mut current = memory.clone();
+
+            'rescan: loop {
+                let hits = match store.vectors.search_similar(&current.content, 5).await {
+                    Ok(hits) => hits,
+                    Err(e) => {
+                        tracing::warn!("consolidation: similarity search failed for {}: {}", current.id, e);
+                        break;
+                    }
+                };
+
+                for (candidate_id, score) in hits {
+                    if candidate_id == current.id || score < DUPLICATE_SIMILARITY || merged_away.contains(&candidate_id) {
+                        continue;
+                    }
+                    let Ok(Some(candidate)) = store.db.get_memory(&candidate_id) else { continue };
+                    if candidate.person != current.person || candidate.project != current.project {
+                        continue;
+                    }
+
+                    // Canonical = whichever id was created first, so re-running
+                    // consolidation converges on the same survivor regardless of
+                    // scan order.
+                    let (canonical, duplicate) = if current.created <= candidate.created {
+                        (current.clone(), candidate)
+                    } else {
+                        (candidate, current.clone())
+                    };
+
+                    let result = merge::merge(&canonical, &duplicate);
+
+                    store.oplog.append(Op::AddMemory(result.clone())).await?;
+                    store.oplog.append(Op::DeleteMemory { id: duplicate.id.clone() }).await?;
+                    store.db.index_memory(&result)?;
+                    store.db.delete_memory(&duplicate.id)?;
+                    store.db.add_redirect(&duplicate.id, &result.id)?;
+
+                    if let Err(e) = store.vectors.delete_point(&duplicate.id).await {
+                        tracing::warn!("consolidation: vector delete failed for {}: {}", duplicate.id, e);
+                    }
+                    if let Some(ref graph) = store.graph {
+                        if let Err(e) = graph.delete_node(&duplicate.id).await {
+                            tracing::warn!("consolidation: neo4j delete failed for {}: {}", duplicate.id, e);
+                        }
+                    }
+
+                    merged_away.insert(duplicate.id.clone());
+                    merges += 1;
+
+                    // `result` becomes the new survivor for this chain;
+                    // restart the scan against it in case it has further
+                    // duplicates among the remaining hits.
+                    current = result;
+                    continue 'rescan;
+                }
+
+                break;
+            }
+        }
+
+        if merges == 0 {
+            Ok("Consolidation: no near-duplicates found.".into())
+        } else {
+            Ok(format!("Consolidation: merged {} near-duplicate pair(s).", merges))
+        }
+    }
+
+    /// `remember`'s duplicate check: a strong semantic match sharing the
+    /// same person/project, to link against instead of writing a new row.
+    pub async fn find_duplicate_of(
+        &self,
+        content: &str,
+        person: Option<&str>,
+        project: Option<&str>,
+    ) -> Result<Option<String>> {
+        let store = self.store.lock().await;
+        let hits = match store.vectors.search_similar(content, 3).await {
+            Ok(hits) => hits,
+            Err(e) => {
+                tracing::warn!("duplicate check: similarity search failed: {}", e);
+                return Ok(None);
+            }
+        };
+        for (candidate_id, score) in hits {
+            if score < DUPLICATE_SIMILARITY {
+                continue;
+            }
+            if let Ok(Some(candidate)) = store.db.get_memory(&candidate_id) {
+                if candidate.person.as_deref() == person && candidate.project.as_deref() == project {
+                    return Ok(Some(candidate_id));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Records a repeat observation of `existing_id` via the same CRDT merge
+    /// `do_consolidate` uses, instead of `remember` inserting a duplicate row.
+    pub async fn link_repeat_observation(&self, existing_id: &str, content: &str) -> Result<String> {
+        let store = self.store.lock().await;
+        let Some(existing) = store.db.get_memory(existing_id)? else {
+            anyhow::bail!("duplicate target {} vanished before merge", existing_id);
+        };
+
+        let now = chrono::Utc::now();
+        let observation = Memory {
+            id: existing.id.clone(),
+            created: now,
+            gate: existing.gate.clone(),
+            person: existing.person.clone(),
+            project: existing.project.clone(),
+            confidence: 0.9,
+            last_accessed: now,
+            access_count: 1,
+            decay_class: existing.decay_class.clone(),
+            // `merge` keeps whichever side is passed as canonical, per its
+            // doc comment — fold the new observation text onto the existing
+            // content here (rather than dropping it) and pass `observation`
+            // as canonical below, so a repeat observation with new detail
+            // survives instead of being silently discarded.
+            content: format!("{}\n\n{}", existing.content, content),
+        };
+
+        let merged = merge::merge(&observation, &existing);
+        store.oplog.append(Op::AddMemory(merged.clone())).await?;
+        store.db.index_memory(&merged)?;
+
+        Ok(format!(
+            "Linked to existing memory (id: {}), now observed {} times.",
+            merged.id, merged.access_count
+        ))
+    }
+}
@@ -1,21 +1,72 @@
 use anyhow::Result;
 use crate::server::MemoryServer;
+use crate::consolidation::decay;
+use crate::types::DecayClass;
 
 impl MemoryServer {
+    /// Maintenance pass: archive memories whose `effective_strength` has
+    /// fallen below `threshold`. `Never`-decaying memories (promissory
+    /// commitments) are always skipped, same as `is_fading`. Invoked from
+    /// `reflect` so the store naturally ages out stale behavioral/fast
+    /// memories while commitments persist.
+    pub async fn do_forget_sweep(&self, threshold: f64) -> Result<String> {
+        let (candidates, archived) = self.do_forget_sweep_counted(threshold).await?;
+        if candidates == 0 {
+            Ok("Decay sweep: nothing below threshold.".into())
+        } else {
+            Ok(format!("Decay sweep: archived {} of {} candidates below threshold", archived, candidates))
+        }
+    }
+
+    /// Same sweep as `do_forget_sweep`, but returns `(candidates, archived)`
+    /// counts instead of a prose summary — what the background decay-sweep
+    /// worker needs to update its `WorkerState`.
+    pub async fn do_forget_sweep_counted(&self, threshold: f64) -> Result<(usize, usize)> {
+        let candidates = {
+            let store = self.store.lock().await;
+            store.db.list_all()?
+                .into_iter()
+                .filter(|m| !matches!(m.decay_class, DecayClass::Never))
+                .filter(|m| decay::effective_strength(m) < threshold)
+                .map(|m| m.id)
+                .collect::<Vec<_>>()
+        };
+
+        if candidates.is_empty() {
+            return Ok((0, 0));
+        }
+
+        let mut archived = 0usize;
+        for id in &candidates {
+            match self.do_forget(id, "effective strength fell below decay threshold").await {
+                Ok(_) => archived += 1,
+                Err(e) => tracing::warn!("decay sweep forget failed for {}: {}", id, e),
+            }
+        }
+
+        Ok((candidates.len(), archived))
+    }
+
     pub async fn do_forget(&self, memory_id: &str, reason: &str) -> Result<String> {
         let store = self.store.lock().await;
 
-        // 1. Remove from SQLite
+        // 1. Log the deletion before mutating anything, mirroring `remember`.
+        store.oplog.append(crate::store::oplog::Op::DeleteMemory {
+            id: memory_id.to_string(),
+        }).await?;
+
+        // 2. Remove from SQLite
         let memory = store.db.delete_memory(memory_id)?;
         if memory.is_none() {
             return Ok(format!("No memory found with id: {}", memory_id));
         }
         let memory = memory.unwrap();
 
-        // 2. Archive with reason
-        let archive_dir = self.store_path.join("archive");
-        std::fs::create_dir_all(&archive_dir)?;
-        let archive_file = archive_dir.join(format!("{}.md", memory_id));
+        // 3. Archive with reason, through the configured backend — same
+        // reasoning as `write_long_term` — so a forgotten memory's content
+        // lands in S3/Garage and under encryption rather than always on
+        // local disk in the clear.
+        let archive_key = format!("archive/{}.md", memory_id);
         let content = format!(
             "---\narchived: {}\nreason: {}\noriginal_gate: {}\n---\n\n{}\n",
             chrono::Utc::now().to_rfc3339(),
@@ -23,22 +74,27 @@ impl MemoryServer {
             memory.gate.as_str(),
             memory.content,
         );
-        std::fs::write(&archive_file, content)?;
+        store.backend.blob_put(&archive_key, content.into_bytes()).await?;
 
-        // 3. Remove from Qdrant
-        if let Some(ref vectors) = store.vectors {
-            if let Err(e) = vectors.delete_point(memory_id).await {
-                tracing::warn!("qdrant delete failed: {}", e);
-            }
+        // 4. Remove from the vector backend
+        if let Err(e) = store.vectors.delete_point(memory_id).await {
+            tracing::warn!("vector delete failed: {}", e);
         }
 
-        // 4. Remove from Neo4j
+        // 5. Remove from Neo4j
         if let Some(ref graph) = store.graph {
             if let Err(e) = graph.delete_node(memory_id).await {
                 tracing::warn!("neo4j delete failed: {}", e);
             }
         }
 
+        // 6. Remove from Postgres
+        if let Some(ref pg) = store.pg {
+            if let Err(e) = pg.delete_memory(memory_id).await {
+                tracing::warn!("postgres delete failed: {}", e);
+            }
+        }
+
         Ok(format!(
             "Forgotten: {} (reason: {}). Archived for accountability.",
             memory_id, reason
@@ -15,12 +15,13 @@ Use `remember` with gate=relational to save what you notice about them.";
 impl MemoryServer {
     pub async fn do_identity(&self) -> Result<String> {
         // Try to load existing identity card
-        match markdown::read_identity(&self.store_path)? {
+        let backend = self.store.lock().await.backend.clone();
+        match markdown::read_identity(backend.as_ref()).await? {
             Some(card) => {
                 let mut output = card.content;
 
                 // Append recent context from last 2 journal entries
-                let recent = journal::recent_journals(&self.store_path, 2)?;
+                let recent = journal::recent_journals(backend.as_ref(), 2).await?;
                 if !recent.is_empty() {
                     output.push_str("\n\n---\nRecent context:\n");
                     // Truncate to ~500 tokens worth
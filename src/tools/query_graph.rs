@@ -0,0 +1,74 @@
+use anyhow::Result;
+use crate::server::MemoryServer;
+
+impl MemoryServer {
+    pub async fn do_query_graph(
+        &self,
+        person: Option<&str>,
+        project: Option<&str>,
+        depth: u32,
+    ) -> Result<String> {
+        if person.is_none() && project.is_none() {
+            anyhow::bail!("query_graph needs a person or project to anchor the traversal");
+        }
+
+        let store = self.store.lock().await;
+
+        // Neo4j is the primary traversal backend; when it's not connected
+        // but `MEMORY_PG_ENABLED=1` is, fall back to the same anchored
+        // walk over the `edges` table instead of giving up entirely.
+        let hits = if let Some(ref graph) = store.graph {
+            graph.find_related_to_anchor(person, project, depth).await?
+        } else if let Some(ref pg) = store.pg {
+            pg.find_related_to_anchor(person, project, depth.clamp(1, 4) as i32).await?
+        } else {
+            return Ok(
+                "Graph traversal isn't available this session — neither Neo4j nor \
+                 the Postgres backend is connected, so relational queries like this \
+                 can't run. Try recall instead."
+                    .into(),
+            );
+        };
+
+        if hits.is_empty() {
+            return Ok("No connected memories found for that anchor.".into());
+        }
+
+        // Memories are first-person prose, not structured data (see
+        // `get_info`'s tool instructions) — render the traversal as
+        // narrative sentences, not a bracketed id-dump.
+        let mut edges = Vec::new();
+        for (id, relation, preview) in hits {
+            let edge = match store.db.get_memory(&id) {
+                Ok(Some(mem)) => format!(
+                    "{} That's from {}, {}.",
+                    mem.content,
+                    mem.created.format("%Y-%m-%d"),
+                    relation_phrase(&relation),
+                ),
+                _ => format!("{} {}.", preview, relation_phrase(&relation)),
+            };
+            edges.push(edge);
+        }
+
+        Ok(format!(
+            "I found {} connected {}:\n\n{}",
+            edges.len(),
+            if edges.len() == 1 { "memory" } else { "memories" },
+            edges.join("\n\n")
+        ))
+    }
+}
+
+/// Turn a Cypher/edge relation type (`related_to`, `RELATED_TO`, ...) into a
+/// phrase that reads naturally at the end of a sentence. `related_to` is by
+/// far the common case (the only one `auto_link` ever creates), so it gets
+/// its own natural phrasing; anything else falls back to naming the edge.
+fn relation_phrase(relation: &str) -> String {
+    let lower = relation.replace('_', " ").to_lowercase();
+    if lower == "related to" {
+        "which relates to this one".into()
+    } else {
+        format!("connected to this one via \"{}\"", lower)
+    }
+}
@@ -1,78 +1,179 @@
 use anyhow::Result;
+use crate::consolidation::decay;
 use crate::server::MemoryServer;
 use crate::store::markdown;
 
+/// Reciprocal Rank Fusion constant. Large enough that the exact rank within
+/// a list matters less than which lists a memory shows up in at all.
+const RRF_K: f64 = 60.0;
+
 impl MemoryServer {
     pub async fn do_recall(&self, query: &str) -> Result<String> {
         let store = self.store.lock().await;
-        let mut results = Vec::new();
-        let mut seen_ids = std::collections::HashSet::new();
 
-        // 1. FTS5 search
-        match store.db.search_fts(query, 5) {
+        // Each backend runs independently and produces its own ordered list
+        // of memory ids. Raw scores aren't comparable across them (FTS5
+        // rank, cosine similarity, graph hop distance), so we fuse by rank
+        // position instead of by score.
+        let mut fused: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+        let mut previews: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+        // 1 & 2. FTS5 and vector similarity don't depend on each other, so
+        // they are joined on this task. `search_fts` is synchronous and
+        // blocks the task while it runs, so the two do not run in parallel.
+        // Each tier's latency is timed inside its own future.
+        let ((fts_outcome, fts_elapsed), (vec_outcome, vec_elapsed)) = tokio::join!(
+            async {
+                let start = std::time::Instant::now();
+                (store.db.search_fts(query, 10), start.elapsed())
+            },
+            async {
+                let start = std::time::Instant::now();
+                (store.vectors.search_similar(query, 10).await, start.elapsed())
+            },
+        );
+
+        match fts_outcome {
             Ok(fts_results) => {
-                for mem in fts_results {
-                    if seen_ids.insert(mem.id.clone()) {
-                        let _ = store.db.touch_memory(&mem.id);
-                        results.push(format!(
-                            "[{}] ({}, {}) {}\n  id: {}",
-                            mem.gate.as_str(),
-                            mem.created.format("%Y-%m-%d"),
-                            mem.person.as_deref().unwrap_or("?"),
-                            mem.content,
-                            mem.id,
-                        ));
-                    }
+                if let Some(ref prom) = self.prom_metrics {
+                    prom.record_recall_tier("fts5", fts_results.len(), fts_elapsed.as_secs_f64());
+                }
+                for (rank, mem) in fts_results.into_iter().enumerate() {
+                    *fused.entry(mem.id.clone()).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                    previews.entry(mem.id.clone()).or_insert_with(|| format_preview(&mem));
                 }
             }
             Err(e) => tracing::warn!("fts5 search failed: {}", e),
         }
 
-        // 2. Qdrant vector search
-        if let Some(ref vectors) = store.vectors {
-            match vectors.search_similar(query, 5).await {
-                Ok(vec_results) => {
-                    for (mem_id, score) in vec_results {
-                        if seen_ids.insert(mem_id.clone()) {
-                            results.push(format!(
-                                "[vector match, score={:.2}] id: {}", score, mem_id
-                            ));
+        match vec_outcome {
+            Ok(vec_results) => {
+                if let Some(ref prom) = self.prom_metrics {
+                    prom.record_recall_tier("vector", vec_results.len(), vec_elapsed.as_secs_f64());
+                }
+                for (rank, (mem_id, _score)) in vec_results.into_iter().enumerate() {
+                    *fused.entry(mem_id).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                }
+            }
+            Err(e) => tracing::warn!("vector search failed: {}", e),
+        }
+
+        // 3. Neo4j graph proximity to whatever FTS/vector already surfaced,
+        // ranked in the order `find_related` returns rows (its query has no
+        // ORDER BY, so hop distance does not set the rank). This
+        // anchors on ids the first two tiers found, so it has to run after
+        // them rather than alongside.
+        if let Some(ref graph) = store.graph {
+            let graph_start = std::time::Instant::now();
+            let mut graph_hits = 0usize;
+            let anchors: Vec<String> = fused.keys().take(3).cloned().collect();
+            for anchor in anchors {
+                match graph.find_related(&anchor, 2).await {
+                    Ok(related) => {
+                        graph_hits += related.len();
+                        for (rank, (rid, relation, preview)) in related.into_iter().enumerate() {
+                            *fused.entry(rid.clone()).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                            previews.entry(rid).or_insert_with(|| format!("[graph: {}] {}", relation, preview));
                         }
                     }
+                    Err(e) => tracing::warn!("neo4j traversal failed: {}", e),
                 }
-                Err(e) => tracing::warn!("qdrant search failed: {}", e),
+            }
+            if let Some(ref prom) = self.prom_metrics {
+                prom.record_recall_tier("graph", graph_hits, graph_start.elapsed().as_secs_f64());
             }
         }
 
-        // 3. Neo4j graph traversal (for sparse results)
-        if results.len() < 3 {
-            if let Some(ref graph) = store.graph {
-                for id in seen_ids.clone().iter().take(2) {
-                    match graph.find_related(id, 2).await {
-                        Ok(related) => {
-                            for (rid, relation, preview) in related {
-                                if seen_ids.insert(rid.clone()) {
-                                    results.push(format!(
-                                        "[graph: {}] {} (id: {})",
-                                        relation, preview, rid
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => tracing::warn!("neo4j traversal failed: {}", e),
+        // 3b. Postgres FTS, when `MEMORY_PG_ENABLED=1` is configured.
+        // Independent of the local FTS5/LMDB index and Qdrant/HNSW vectors
+        // above — a separate full-text tier over the same content living in
+        // the managed database, fused in the same RRF pass as everything
+        // else.
+        if let Some(ref pg) = store.pg {
+            let pg_start = std::time::Instant::now();
+            match pg.search_fts(query, 10).await {
+                Ok(pg_results) => {
+                    if let Some(ref prom) = self.prom_metrics {
+                        prom.record_recall_tier("postgres", pg_results.len(), pg_start.elapsed().as_secs_f64());
+                    }
+                    for (rank, mem) in pg_results.into_iter().enumerate() {
+                        *fused.entry(mem.id.clone()).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                        previews.entry(mem.id.clone()).or_insert_with(|| format_preview(&mem));
                     }
                 }
+                Err(e) => tracing::warn!("postgres fts search failed: {}", e),
             }
         }
 
-        // 4. Fallback: grep markdown
+        // Resolve every fused id up front so decay strength can blend into
+        // the score *before* truncating to the top 5 — otherwise a stale
+        // match that only made the cut on raw rank fusion would still push
+        // out a fresher one that belonged there instead.
+        let mut ranked: Vec<(String, f64, Option<crate::types::Memory>)> = Vec::new();
+        for (id, rrf_score) in fused {
+            // `id` may be a graph edge or FTS row cached before consolidation
+            // merged it away; follow the redirect to its surviving canonical
+            // memory rather than showing a dead id.
+            let resolved = match store.db.get_memory(&id) {
+                Ok(Some(_)) => id.clone(),
+                Ok(None) => store.db.resolve_redirect(&id)?.unwrap_or_else(|| id.clone()),
+                Err(_) => id.clone(),
+            };
+            let memory = store.db.get_memory(&resolved).ok().flatten();
+            // Blend rank fusion with retrieval-time decay strength, same
+            // reasoning as `effective_strength`'s own doc comment: a
+            // confident, frequently-accessed, recently-touched memory should
+            // outrank one that merely matched the query text better.
+            let score = match &memory {
+                Some(mem) => rrf_score * decay::effective_strength(mem),
+                None => rrf_score,
+            };
+            ranked.push((id, score, memory));
+        }
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.truncate(5);
+
+        let mut results = Vec::new();
+        for (id, score, memory) in &ranked {
+            let preview = match memory {
+                Some(mem) => {
+                    let _ = store.db.touch_memory(&mem.id);
+                    // Log the bump too, so a restart's `oplog.rebuild` replay
+                    // doesn't reset access_count/last_accessed back to
+                    // whatever the last checkpoint had.
+                    if let Err(e) = store.oplog.append(crate::store::oplog::Op::BumpAccess {
+                        id: mem.id.clone(),
+                    }).await {
+                        tracing::warn!("oplog: failed to log access bump for {}: {}", mem.id, e);
+                    }
+                    format!(
+                        "[{}] ({}, {}, decay={:.2}) {}\n  id: {}",
+                        mem.gate.as_str(),
+                        mem.created.format("%Y-%m-%d"),
+                        mem.person.as_deref().unwrap_or("?"),
+                        decay::effective_strength(mem),
+                        mem.content,
+                        mem.id,
+                    )
+                }
+                None => previews.get(id).cloned().unwrap_or_else(|| format!("id: {}", id)),
+            };
+            results.push(format!("(score={:.4}) {}", score, preview));
+        }
+
+        // 4. Fallback: grep markdown, but only when RRF across every tier
+        // above still turned up nothing at all.
         if results.is_empty() {
-            match markdown::search_all(&self.store_path, query) {
+            let grep_start = std::time::Instant::now();
+            match markdown::search_all(store.backend.as_ref(), query).await {
                 Ok(grep_results) => {
                     for content in grep_results.iter().take(3) {
                         let preview: String = content.chars().take(300).collect();
                         results.push(format!("[file search] {}", preview));
                     }
+                    if let Some(ref prom) = self.prom_metrics {
+                        prom.record_recall_tier("grep_fallback", results.len(), grep_start.elapsed().as_secs_f64());
+                    }
                 }
                 Err(e) => tracing::warn!("markdown search failed: {}", e),
             }
@@ -89,3 +190,14 @@ impl MemoryServer {
         }
     }
 }
+
+fn format_preview(mem: &crate::types::Memory) -> String {
+    format!(
+        "[{}] ({}, {}) {}\n  id: {}",
+        mem.gate.as_str(),
+        mem.created.format("%Y-%m-%d"),
+        mem.person.as_deref().unwrap_or("?"),
+        mem.content,
+        mem.id,
+    )
+}
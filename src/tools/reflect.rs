@@ -1,34 +1,63 @@
 use anyhow::Result;
 use crate::server::MemoryServer;
-use crate::consolidation::{digest, journal};
+use crate::consolidation::{decay, digest, journal};
 use crate::extract;
+use crate::store::backend::Storage;
 use crate::store::markdown;
 use crate::types::IdentityCard;
 
 impl MemoryServer {
+    #[tracing::instrument(skip(self))]
     pub async fn do_reflect(&self) -> Result<String> {
         let mut report = Vec::new();
+        let backend = self.store.lock().await.backend.clone();
 
-        // 1. Consolidate old journals into weekly digests
-        match digest::consolidate_journals(&self.store_path, &self.api_key).await {
-            Ok(Some(msg)) => report.push(msg),
+        // 1. Log which journals are about to be archived, before
+        // consolidation touches anything, so the op log records them even
+        // if this pass crashes mid-consolidation. Replay does not act on
+        // these ops.
+        let stale = journal::stale_journals(backend.as_ref(), 14).await?;
+        if !stale.is_empty() {
+            let store = self.store.lock().await;
+            for date in &stale {
+                store.oplog.append(crate::store::oplog::Op::ArchiveJournal {
+                    date: date.format("%Y-%m-%d").to_string(),
+                }).await?;
+            }
+        }
+
+        // 2. Consolidate old journals into weekly digests
+        match digest::consolidate_journals(backend.as_ref(), &self.api_key).await {
+            Ok(Some((msg, tokens))) => {
+                if let Some(ref metrics) = self.metrics {
+                    metrics.consolidation_tokens(tokens);
+                }
+                report.push(msg);
+            }
             Ok(None) => report.push("No journals old enough to consolidate.".into()),
-            Err(e) => report.push(format!("Journal consolidation failed: {}", e)),
+            Err(e) => {
+                if let Some(ref metrics) = self.metrics {
+                    metrics.api_error("consolidate_journals");
+                }
+                report.push(format!("Journal consolidation failed: {}", e));
+            }
         }
 
-        // 2. Regenerate identity card from recent memories
-        let recent = journal::recent_journals(&self.store_path, 5)?;
+        // 3. Regenerate identity card from recent memories
+        let recent = journal::recent_journals(backend.as_ref(), 5).await?;
         if !recent.is_empty() {
             match extract::regenerate_identity(&recent, &self.api_key).await {
                 Ok(new_identity) => {
-                    // Archive old identity
-                    if let Ok(Some(old)) = markdown::read_identity(&self.store_path) {
-                        let archive_dir = self.store_path.join("archive/identity");
-                        std::fs::create_dir_all(&archive_dir)?;
-                        let archive_file = archive_dir.join(format!(
-                            "{}.md", chrono::Utc::now().format("%Y-%m-%d")
-                        ));
-                        std::fs::write(&archive_file, &old.content)?;
+                    let store = self.store.lock().await;
+
+                    // Archive old identity. Same `Storage` backend as the
+                    // live card, so the archived copy follows the store to
+                    // S3/Garage and under encryption too.
+                    if let Ok(Some(old)) = markdown::read_identity(store.backend.as_ref()).await {
+                        let archive_key = format!(
+                            "archive/identity/{}.md", chrono::Utc::now().format("%Y-%m-%d")
+                        );
+                        store.backend.blob_put(&archive_key, old.content.into_bytes()).await?;
                     }
 
                     let card = IdentityCard {
@@ -37,13 +66,22 @@ impl MemoryServer {
                         content: new_identity.clone(),
                         last_updated: chrono::Utc::now(),
                     };
-                    markdown::write_identity(&self.store_path, &card)?;
+                    // A CRDT merge, not an overwrite — see `write_identity` —
+                    // so a concurrent session regenerating the card at the
+                    // same time can't silently clobber this update.
+                    markdown::write_identity(store.backend.as_ref(), &store.oplog, &card).await?;
                     report.push("Identity card regenerated.".into());
                 }
                 Err(e) => report.push(format!("Identity regeneration failed: {}", e)),
             }
         }
 
+        // 4. Sweep memories whose effective strength has decayed away
+        match self.do_forget_sweep(decay::FORGET_THRESHOLD).await {
+            Ok(msg) => report.push(msg),
+            Err(e) => report.push(format!("Decay sweep failed: {}", e)),
+        }
+
         if report.is_empty() {
             Ok("Reflection complete. Nothing to consolidate.".into())
         } else {
@@ -4,6 +4,7 @@ use chrono::Utc;
 use crate::server::MemoryServer;
 use crate::types::{DecayClass, Gate, JournalEntry, Memory};
 use crate::store::markdown;
+use crate::store::oplog::Op;
 
 impl MemoryServer {
     pub async fn do_remember(
@@ -18,6 +19,18 @@ impl MemoryServer {
                 "invalid gate '{}'. use: behavioral, relational, epistemic, promissory", gate
             ))?;
 
+        if let Some(ref prom) = self.prom_metrics {
+            prom.record_remember(gate.as_str());
+        }
+
+        // A strong semantic match for the same person/project is treated as
+        // a repeat observation, not a new memory — otherwise re-remembering
+        // the same fact just accumulates duplicate rows with reset
+        // `access_count` (see `find_duplicate_of`).
+        if let Some(existing_id) = self.find_duplicate_of(content, person, project).await? {
+            return self.link_repeat_observation(&existing_id, content).await;
+        }
+
         let now = Utc::now();
         let id = format!(
             "mem_{}_{}",
@@ -38,7 +51,13 @@ impl MemoryServer {
             content: content.to_string(),
         };
 
-        // 1. Write to today's journal
+        // 1. Append to the op log before anything else is mutated, so a
+        // crash partway through this call still leaves a replayable record
+        // of the memory.
+        let store = self.store.lock().await;
+        store.oplog.append(Op::AddMemory(memory.clone())).await?;
+
+        // 2. Write to today's journal
         let entry = JournalEntry {
             timestamp: now,
             gate: gate.clone(),
@@ -46,19 +65,16 @@ impl MemoryServer {
             person: person.map(|s| s.to_string()),
             project: project.map(|s| s.to_string()),
         };
-        markdown::write_journal_entry(&self.store_path, &entry)?;
+        markdown::write_journal_entry(store.backend.as_ref(), &entry).await?;
 
-        // 2. Write long-term memory file
-        markdown::write_long_term(&self.store_path, &memory)?;
+        // 3. Write long-term memory file
+        markdown::write_long_term(store.backend.as_ref(), &memory).await?;
 
-        // 3. Index in FTS5, embed in Qdrant, add to Neo4j graph
-        let store = self.store.lock().await;
+        // 4. Index in FTS5, embed in the vector backend, add to Neo4j graph
         store.db.index_memory(&memory)?;
 
-        if let Some(ref vectors) = store.vectors {
-            if let Err(e) = vectors.embed_and_store(&id, content, person, project).await {
-                tracing::warn!("qdrant embed failed: {}", e);
-            }
+        if let Err(e) = store.vectors.embed_and_store(&id, content, person, project).await {
+            tracing::warn!("vector embed failed: {}", e);
         }
 
         if let Some(ref graph) = store.graph {
@@ -67,8 +83,19 @@ impl MemoryServer {
             ).await {
                 tracing::warn!("neo4j upsert failed: {}", e);
             }
-            if let Err(e) = graph.auto_link(&id, person, project).await {
-                tracing::warn!("neo4j auto-link failed: {}", e);
+            match graph.auto_link(&id, person, project).await {
+                Ok(linked) => log_auto_link_edges(&store, &id, &linked).await,
+                Err(e) => tracing::warn!("neo4j auto-link failed: {}", e),
+            }
+        }
+
+        if let Some(ref pg) = store.pg {
+            if let Err(e) = pg.index_memory(&memory).await {
+                tracing::warn!("postgres index failed: {}", e);
+            }
+            match pg.auto_link(&id, person, project).await {
+                Ok(linked) => log_auto_link_edges(&store, &id, &linked).await,
+                Err(e) => tracing::warn!("postgres auto-link failed: {}", e),
             }
         }
 
@@ -80,3 +107,18 @@ impl MemoryServer {
         ))
     }
 }
+
+/// Log one `Op::AddEdge` per id `auto_link` linked `from_id` to, so edges
+/// created in Neo4j/Postgres are recorded in the op log. `oplog.rebuild`
+/// does not replay these ops into either graph store.
+async fn log_auto_link_edges(store: &crate::store::Store, from_id: &str, linked: &[String]) {
+    for to_id in linked {
+        if let Err(e) = store.oplog.append(Op::AddEdge {
+            from_id: from_id.to_string(),
+            to_id: to_id.clone(),
+            relation: "related_to".to_string(),
+        }).await {
+            tracing::warn!("oplog: failed to log edge {} -> {}: {}", from_id, to_id, e);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+
+use crate::consolidation::decay;
+use crate::server::MemoryServer;
+use crate::store::markdown;
+
+const DEFAULT_SWEEP_INTERVAL_SECS: u64 = 300;
+
+/// Last-run snapshot for one background worker, returned by the `workers`
+/// tool's "get" action.
+#[derive(Debug, Clone, Default)]
+pub struct WorkerState {
+    pub last_run: Option<DateTime<Utc>>,
+    pub items_processed: u64,
+    pub items_changed: u64,
+}
+
+/// Tunable knobs read fresh on every tick, so the `workers` tool's "set"
+/// action takes effect on the next run without restarting the server.
+/// `fading_threshold` is stored as millis-of-threshold in an `AtomicU64`
+/// (`f64` has no atomic type) rather than behind a `Mutex`, since it's only
+/// ever read or replaced wholesale, never read-modify-written.
+pub struct WorkerConfig {
+    sweep_interval_secs: AtomicU64,
+    fading_threshold_millis: AtomicU64,
+}
+
+impl WorkerConfig {
+    fn new() -> Self {
+        Self {
+            sweep_interval_secs: AtomicU64::new(DEFAULT_SWEEP_INTERVAL_SECS),
+            fading_threshold_millis: AtomicU64::new((decay::FORGET_THRESHOLD * 1000.0) as u64),
+        }
+    }
+
+    pub fn sweep_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.sweep_interval_secs.load(Ordering::Relaxed))
+    }
+
+    pub fn fading_threshold(&self) -> f64 {
+        self.fading_threshold_millis.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    pub fn set_sweep_interval_secs(&self, secs: u64) {
+        self.sweep_interval_secs.store(secs.max(1), Ordering::Relaxed);
+    }
+
+    pub fn set_fading_threshold(&self, threshold: f64) {
+        self.fading_threshold_millis.store((threshold.max(0.0) * 1000.0) as u64, Ordering::Relaxed);
+    }
+}
+
+/// Background maintenance: a decay sweep (archive memories whose effective
+/// strength has faded below threshold) and a store-reconciliation pass
+/// (re-index long-term files `do_remember` failed to propagate into SQLite
+/// or the vector backend after `tracing::warn!`-and-continue errors). Both
+/// run on the same tunable interval, read from `config` on every tick.
+pub struct Workers {
+    pub config: WorkerConfig,
+    pub decay_sweep: Mutex<WorkerState>,
+    pub reconcile: Mutex<WorkerState>,
+}
+
+impl Workers {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            config: WorkerConfig::new(),
+            decay_sweep: Mutex::new(WorkerState::default()),
+            reconcile: Mutex::new(WorkerState::default()),
+        })
+    }
+
+    /// Spawn the two maintenance loops. `server` is cheap to clone (an
+    /// `Arc`-backed handle, same as every `#[tool]` method receives), so
+    /// each loop owns its own clone rather than borrowing.
+    pub fn spawn(server: MemoryServer) {
+        let decay_server = server.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(decay_server.workers.config.sweep_interval()).await;
+                decay_server.run_decay_sweep().await;
+            }
+        });
+
+        let reconcile_server = server.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(reconcile_server.workers.config.sweep_interval()).await;
+                reconcile_server.run_reconcile().await;
+            }
+        });
+    }
+}
+
+impl MemoryServer {
+    async fn run_decay_sweep(&self) {
+        let threshold = self.workers.config.fading_threshold();
+        match self.do_forget_sweep_counted(threshold).await {
+            Ok((candidates, archived)) => {
+                let mut state = self.workers.decay_sweep.lock().await;
+                state.last_run = Some(Utc::now());
+                state.items_processed += candidates as u64;
+                state.items_changed += archived as u64;
+            }
+            Err(e) => tracing::warn!("decay sweep worker failed: {}", e),
+        }
+        self.refresh_decay_gauges().await;
+    }
+
+    /// Surfaces `decay::compute_decay_score`/`is_fading` — otherwise unused
+    /// outside their own module — as Prometheus observations, piggybacking
+    /// on the decay sweep's tick rather than a third timer.
+    async fn refresh_decay_gauges(&self) {
+        let Some(ref prom) = self.prom_metrics else { return };
+        let memories = {
+            let store = self.store.lock().await;
+            match store.db.list_all() {
+                Ok(memories) => memories,
+                Err(e) => {
+                    tracing::warn!("decay gauge refresh: failed to list memories: {}", e);
+                    return;
+                }
+            }
+        };
+
+        prom.set_memories_total(memories.len() as i64);
+        let mut fading = 0i64;
+        for memory in &memories {
+            prom.observe_decay_score(decay::compute_decay_score(memory));
+            if decay::is_fading(memory) {
+                fading += 1;
+            }
+        }
+        prom.set_fading_memories(fading);
+    }
+
+    async fn run_reconcile(&self) {
+        let backend = self.store.lock().await.backend.clone();
+        let long_term = match markdown::list_long_term_memories(backend.as_ref()).await {
+            Ok(memories) => memories,
+            Err(e) => {
+                tracing::warn!("reconcile worker: failed to read long-term files: {}", e);
+                return;
+            }
+        };
+
+        let mut reindexed = 0u64;
+        {
+            let store = self.store.lock().await;
+            for memory in &long_term {
+                let already_indexed = matches!(store.db.get_memory(&memory.id), Ok(Some(_)));
+                if !already_indexed {
+                    if let Err(e) = store.db.index_memory(memory) {
+                        tracing::warn!("reconcile: failed to reindex {} in sqlite: {}", memory.id, e);
+                        continue;
+                    }
+                }
+
+                // Checked independently of `already_indexed`: `do_remember`
+                // warns-and-continues on a failed `embed_and_store`, so a
+                // memory can be fully indexed in SQLite while still missing
+                // from the vector backend. Re-check here rather than
+                // `continue`-ing past it, or this worker never catches the
+                // exact partial-failure case it exists to repair.
+                let already_embedded = match store.vectors.contains(&memory.id).await {
+                    Ok(found) => found,
+                    Err(e) => {
+                        tracing::warn!("reconcile: failed to check vector index for {}: {}", memory.id, e);
+                        true
+                    }
+                };
+
+                if already_indexed && already_embedded {
+                    continue;
+                }
+
+                if !already_embedded {
+                    if let Err(e) = store.vectors.embed_and_store(
+                        &memory.id, &memory.content,
+                        memory.person.as_deref(), memory.project.as_deref(),
+                    ).await {
+                        tracing::warn!("reconcile: failed to re-embed {}: {}", memory.id, e);
+                    }
+                }
+                reindexed += 1;
+            }
+        }
+
+        let mut state = self.workers.reconcile.lock().await;
+        state.last_run = Some(Utc::now());
+        state.items_processed += long_term.len() as u64;
+        state.items_changed += reindexed;
+    }
+
+    /// Backing implementation for the `workers` MCP tool: "get" reports
+    /// both workers' last-run state, "set" tunes `WorkerConfig` live.
+    pub async fn do_workers(
+        &self,
+        action: &str,
+        sweep_interval_secs: Option<u64>,
+        fading_threshold: Option<f64>,
+    ) -> String {
+        match action {
+            "set" => {
+                if let Some(secs) = sweep_interval_secs {
+                    self.workers.config.set_sweep_interval_secs(secs);
+                }
+                if let Some(threshold) = fading_threshold {
+                    self.workers.config.set_fading_threshold(threshold);
+                }
+                format!(
+                    "Workers updated: sweep_interval={:?}, fading_threshold={:.3}",
+                    self.workers.config.sweep_interval(),
+                    self.workers.config.fading_threshold(),
+                )
+            }
+            "get" => {
+                let decay = self.workers.decay_sweep.lock().await.clone();
+                let reconcile = self.workers.reconcile.lock().await.clone();
+                format!(
+                    "decay_sweep: last_run={:?}, processed={}, archived={}\n\
+                     reconcile: last_run={:?}, processed={}, reindexed={}\n\
+                     config: sweep_interval={:?}, fading_threshold={:.3}",
+                    decay.last_run, decay.items_processed, decay.items_changed,
+                    reconcile.last_run, reconcile.items_processed, reconcile.items_changed,
+                    self.workers.config.sweep_interval(), self.workers.config.fading_threshold(),
+                )
+            }
+            other => format!("Unknown workers action: '{}' (expected \"get\" or \"set\")", other),
+        }
+    }
+}